@@ -0,0 +1,25 @@
+use clap::Args;
+
+#[derive(Debug, Args)]
+pub(crate) struct BoxesOptions {
+    /// Image file of a marker layer, aligned cell for cell with the sprite sheet, where boxes are painted
+    #[arg(short = 'm', long = "markers")]
+    pub markers: String,
+
+    /// Colour of the hurtboxes in the marker layer, as a hex code
+    #[arg(long = "hurtbox", default_value_t = String::from("ff0000"))]
+    pub hurtbox: String,
+
+    /// Colour of the hitboxes in the marker layer, as a hex code
+    #[arg(long = "hitbox", default_value_t = String::from("00ff00"))]
+    pub hitbox: String,
+
+    /// Image file of the target sprite sheet
+    #[arg(short = 'i', long = "image")]
+    pub image: String,
+
+    /// Size of the target sprite sheet, e.g. `40x50` for pixel size, `3-3` for cell count,
+    /// `single` for a single image; `10` is the same as `10x10`; can be omitted for sheets saved by sheety
+    #[arg(short = 's', long = "size")]
+    pub size: Option<String>,
+}
@@ -10,7 +10,19 @@ pub(crate) struct CatOptions {
     #[arg(short = 's', long = "size")]
     pub sizes: Vec<String>,
 
-    /// The default size, use it instead of -s if all sprite sheets have the same srpite size
+    /// The default size, use it instead of -s if all sprite sheets have the same srpite size;
+    /// both can be omitted if all sprite sheets were saved by sheety
     #[arg(short = 'S', long = "default_size", default_value_t = String::new())]
     pub default_size: String,
+
+    /// Fit sprites of different sizes to a common size: `pad:<anchor>` pads them to the largest size,
+    /// `crop:<anchor>` crops them to the smallest size, and `scale:<anchor>` scales them to fit in the largest size.
+    /// Anchors are `top-left`, `top`, `top-right`, `left`, `center`, `right`, `bottom-left`, `bottom`, `bottom-right`,
+    /// or a custom pivot such as `0.5,0.9`; the anchor can be omitted to use `center`
+    #[arg(short = 'f', long = "fit")]
+    pub fit: Option<String>,
+
+    /// The size to fit sprites to with --fit instead of the largest/smallest size, e.g. `64x64`
+    #[arg(short = 'F', long = "fit-size")]
+    pub fit_size: Option<String>,
 }
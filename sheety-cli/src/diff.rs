@@ -0,0 +1,25 @@
+use clap::Args;
+
+#[derive(Debug, Args)]
+pub(crate) struct DiffOptions {
+    /// Image file of the old version of the sprite sheet
+    #[arg(short = 'b', long = "base")]
+    pub base: String,
+
+    /// Size of the old version of the sprite sheet, same as `--size` if omitted
+    #[arg(long = "base-size")]
+    pub base_size: Option<String>,
+
+    /// Colour of the changed pixels in the highlight image, as a hex code (e.g. `ff0000`)
+    #[arg(short = 'c', long = "colour", default_value_t = String::from("ff0000"))]
+    pub colour: String,
+
+    /// Image file of the target sprite sheet
+    #[arg(short = 'i', long = "image")]
+    pub image: String,
+
+    /// Size of the target sprite sheet, e.g. `40x50` for pixel size, `3-3` for cell count,
+    /// `single` for a single image; `10` is the same as `10x10`; can be omitted for sheets saved by sheety
+    #[arg(short = 's', long = "size")]
+    pub size: Option<String>,
+}
@@ -0,0 +1,17 @@
+use clap::Args;
+
+#[derive(Debug, Args)]
+pub(crate) struct HoldOptions {
+    /// The amount of frames to hold each sprite for, separated by commas (e.g. `2,1,1,3`),
+    /// or a single amount for all sprites
+    pub holds: String,
+
+    /// Image file of the target sprite sheet
+    #[arg(short = 'i', long = "image")]
+    pub image: String,
+
+    /// Size of the target sprite sheet, e.g. `40x50` for pixel size, `3-3` for cell count,
+    /// `single` for a single image; `10` is the same as `10x10`; can be omitted for sheets saved by sheety
+    #[arg(short = 's', long = "size")]
+    pub size: Option<String>,
+}
@@ -0,0 +1,25 @@
+use clap::Args;
+
+#[derive(Debug, Args)]
+pub(crate) struct LintOptions {
+    /// Pixels are visible if their alpha is greater than the threshold, `0` makes every visible pixel count
+    #[arg(short = 't', long = "threshold", default_value_t = 0)]
+    pub threshold: u8,
+
+    /// Sprites with fewer visible pixels than this are reported as almost empty
+    #[arg(long = "min-pixels", default_value_t = 4)]
+    pub min_pixels: usize,
+
+    /// How much the size of a sprite can differ from most sprites, e.g. `0.5` for half as big or bigger
+    #[arg(long = "tolerance", default_value_t = 0.5)]
+    pub tolerance: f32,
+
+    /// Image file of the target sprite sheet
+    #[arg(short = 'i', long = "image")]
+    pub image: String,
+
+    /// Size of the target sprite sheet, e.g. `40x50` for pixel size, `3-3` for cell count,
+    /// `single` for a single image; `10` is the same as `10x10`; can be omitted for sheets saved by sheety
+    #[arg(short = 's', long = "size")]
+    pub size: Option<String>,
+}
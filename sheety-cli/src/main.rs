@@ -4,22 +4,59 @@
 
 extern crate anyhow;
 extern crate clap;
+extern crate image;
 extern crate sheety;
 
+mod boxes;
 mod cat;
 mod del;
+mod diff;
+mod hold;
+mod lint;
+mod mask;
+mod mirror_dirs;
+mod name;
+mod nineslice;
+mod outline;
+mod pingpong;
+mod pivot;
+mod preview;
+mod retime;
 mod rev;
+mod selection;
+mod seq;
 mod slc;
+mod zip;
 
-use std::{ops::Range, path::PathBuf};
+use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, bail, Context, Result};
+use boxes::BoxesOptions;
 use cat::CatOptions;
 use clap::{Parser, Subcommand};
 use del::DelOptions;
+use diff::DiffOptions;
+use hold::HoldOptions;
+use image::Rgba;
+use lint::LintOptions;
+use mask::MaskOptions;
+use mirror_dirs::MirrorDirsOptions;
+use name::NameOptions;
+use nineslice::NineSliceOptions;
+use outline::OutlineOptions;
+use pingpong::PingPongOptions;
+use pivot::PivotOptions;
+use preview::PreviewOptions;
+use retime::RetimeOptions;
 use rev::RevOptions;
-use sheety::{Distribution, SpriteSheet, UnorderedSpriteSheet};
+use selection::Selection;
+use seq::SeqOptions;
+use sheety::{
+    Anchor, AnimationTag, Background, Direction, DirectionalSheet, Distribution, Fit, NineSlice,
+    Remainder, SheetMetadata, SliceMode, Sprite, SpriteCell, SpriteSheet, UnorderedSpriteSheet,
+};
 use slc::SlcOptions;
+use zip::ZipOptions;
 
 fn main() -> Result<()> {
     ParsedCommand::parse(Cli::parse())?.execute()
@@ -38,6 +75,25 @@ struct Cli {
     /// The file to write the result into
     #[arg(short = 'o', long = "output", global = true, default_value_t = String::from("./sheety-result.png"))]
     output: String,
+
+    /// Drop the pixels left over on the right and at the bottom of images that aren't made of whole cells,
+    /// instead of failing
+    #[arg(long = "allow-remainder", global = true)]
+    allow_remainder: bool,
+
+    /// Which pixels are background, to find empty cells in opaque sheets: `auto` for the colour of the corners,
+    /// a colour such as `ff00ff`, or `alpha:<threshold>`; colours take a tolerance, e.g. `auto:8`
+    #[arg(long = "background", global = true)]
+    background: Option<String>,
+
+    /// Make the background pixels transparent; the background defaults to `auto`
+    #[arg(long = "key-out", global = true)]
+    key_out: bool,
+
+    /// Read PNG images one line of cells at a time, so that very large sheets don't have to fit in memory;
+    /// each cell then owns a copy of its pixels
+    #[arg(long = "streaming", global = true)]
+    streaming: bool,
 }
 
 #[derive(Debug, Subcommand)]
@@ -53,23 +109,71 @@ enum Command {
 
     /// Reverse a sprite sheet
     Rev(RevOptions),
+
+    /// Build a new sprite sheet from a sequence of sprites of a sprite sheet, duplicates allowed
+    Seq(SeqOptions),
+
+    /// Turn the sprites of a sprite sheet into a ping-pong loop
+    #[command(name = "pingpong")]
+    PingPong(PingPongOptions),
+
+    /// Hold each sprite of a sprite sheet for a given amount of frames
+    Hold(HoldOptions),
+
+    /// Resample the sprites of a sprite sheet from one frame rate to another
+    Retime(RetimeOptions),
+
+    /// Zip one or more sprite sheets together frame by frame
+    Zip(ZipOptions),
+
+    /// Synthesise missing directions of a sprite sheet with one line per direction, by mirroring existing ones
+    MirrorDirs(MirrorDirsOptions),
+
+    /// Name the sprites of a sprite sheet, so that they can be selected by name
+    Name(NameOptions),
+
+    /// Set the pivots of the sprites of a sprite sheet, for all of them or from a marker layer
+    Pivot(PivotOptions),
+
+    /// Set the hurtboxes and hitboxes of the sprites of a sprite sheet from a marker layer
+    Boxes(BoxesOptions),
+
+    /// Make the 1-bit collision masks of the sprites of a sprite sheet, as a mask sheet and a binary blob
+    Mask(MaskOptions),
+
+    /// Trace outline polygons around the sprites of a sprite sheet, for tight meshes
+    Outline(OutlineOptions),
+
+    /// Give nine-slice borders to the sprites of a sprite sheet, and preview them resized as nine-slices
+    #[command(name = "nineslice")]
+    NineSlice(NineSliceOptions),
+
+    /// Draw a preview of a sprite sheet, with a grid and the index and the name of each sprite
+    Preview(PreviewOptions),
+
+    /// Compare two versions of a sprite sheet, printing the changed cells and highlighting the changed pixels
+    Diff(DiffOptions),
+
+    /// Check a sprite sheet for likely mistakes, like a wrong size, exiting with an error if there are any
+    Lint(LintOptions),
 }
 
 #[derive(Debug)]
 enum ParsedCommand {
     Cat {
         files: Vec<FileDiv>,
+        fit: Option<FitOptions>,
         dist: Distribution,
         output: PathBuf,
     },
     Del {
-        indices: UnboundRange,
+        indices: Selection,
         file: FileDiv,
         dist: Distribution,
         output: PathBuf,
     },
     Slc {
-        indices: UnboundRange,
+        indices: Selection,
         file: FileDiv,
         dist: Distribution,
         output: PathBuf,
@@ -79,60 +183,139 @@ enum ParsedCommand {
         dist: Distribution,
         output: PathBuf,
     },
+    Seq {
+        sequence: Selection,
+        file: FileDiv,
+        dist: Distribution,
+        output: PathBuf,
+    },
+    PingPong {
+        repeat_endpoints: bool,
+        file: FileDiv,
+        dist: Distribution,
+        output: PathBuf,
+    },
+    Hold {
+        holds: Vec<usize>,
+        file: FileDiv,
+        dist: Distribution,
+        output: PathBuf,
+    },
+    Retime {
+        from: f32,
+        to: f32,
+        file: FileDiv,
+        dist: Distribution,
+        output: PathBuf,
+    },
+    Zip {
+        layout: ZipLayout,
+        files: Vec<FileDiv>,
+        dist: Distribution,
+        output: PathBuf,
+    },
+    MirrorDirs {
+        from: Vec<Direction>,
+        to: Vec<Direction>,
+        file: FileDiv,
+        output: PathBuf,
+    },
+    Name {
+        pattern: String,
+        file: FileDiv,
+        dist: Distribution,
+        output: PathBuf,
+    },
+    Pivot {
+        all: Option<Anchor>,
+        markers: Option<(PathBuf, Rgba<u8>)>,
+        file: FileDiv,
+        output: PathBuf,
+    },
+    Boxes {
+        markers: PathBuf,
+        hurtbox: Rgba<u8>,
+        hitbox: Rgba<u8>,
+        file: FileDiv,
+        output: PathBuf,
+    },
+    Mask {
+        threshold: u8,
+        blob: Option<PathBuf>,
+        file: FileDiv,
+        output: PathBuf,
+    },
+    Outline {
+        threshold: u8,
+        max_vertices: usize,
+        convex: bool,
+        triangulate: bool,
+        file: FileDiv,
+        output: PathBuf,
+    },
+    NineSlice {
+        borders: Option<NineSlice>,
+        to: Option<(usize, usize)>,
+        mode: SliceMode,
+        file: FileDiv,
+        output: PathBuf,
+    },
+    Preview {
+        options: sheety::PreviewOptions,
+        file: FileDiv,
+        output: PathBuf,
+    },
+    Diff {
+        base: FileDiv,
+        colour: Rgba<u8>,
+        file: FileDiv,
+        output: PathBuf,
+    },
+    Lint {
+        options: sheety::LintOptions,
+        file: FileDiv,
+    },
 }
 
 impl ParsedCommand {
     fn parse(cli: Cli) -> Result<Self> {
+        let loading = Loading {
+            allow_remainder: cli.allow_remainder,
+            background: cli
+                .background
+                .as_deref()
+                .map(parse_background)
+                .transpose()?,
+            key_out: cli.key_out,
+            streaming: cli.streaming,
+        };
+
         Ok(match cli.command {
-            Command::Cat(options) => {
-                if options.sizes.is_empty() && !options.default_size.is_empty() {
-                    // no sizes given and a default size given
-                    Self::Cat {
-                        files: options
-                            .images
-                            .into_iter()
-                            .map(|f| {
-                                Ok(FileDiv {
-                                    file_path: PathBuf::from(f),
-                                    div: Div::parse(options.default_size.as_str())?,
-                                })
-                            })
-                            .collect::<Result<Vec<FileDiv>>>()?,
-                        dist: parse_distribution(cli.distribution.as_str())?,
-                        output: PathBuf::from(cli.output),
-                    }
-                } else if options.sizes.len() == options.images.len() {
-                    // a size given for each sprite
-                    Self::Cat {
-                        files: std::iter::zip(options.images, options.sizes)
-                            .map(|(img, size)| {
-                                Ok(FileDiv {
-                                    file_path: PathBuf::from(img),
-                                    div: Div::parse(size.as_str())?,
-                                })
-                            })
-                            .collect::<Result<Vec<FileDiv>>>()?,
-                        dist: parse_distribution(cli.distribution.as_str())?,
-                        output: PathBuf::from(cli.output),
-                    }
-                } else {
-                    bail!("size count should be the same as the image count, or there should be a default size and nothing else");
-                }
-            }
+            Command::Cat(options) => Self::Cat {
+                files: parse_files(options.images, options.sizes, options.default_size, loading)?,
+                fit: options
+                    .fit
+                    .map(|fit| FitOptions::parse(fit.as_str(), options.fit_size.as_deref()))
+                    .transpose()?,
+                dist: parse_distribution(cli.distribution.as_str())?,
+                output: PathBuf::from(cli.output),
+            },
             Command::Del(options) => Self::Del {
-                indices: UnboundRange::parse(options.indices.as_str())?,
+                indices: Selection::parse(options.indices.as_str())?,
                 file: FileDiv {
                     file_path: PathBuf::from(options.image),
-                    div: Div::parse(options.size.as_str())?,
+                    div: Div::parse_or_embedded(options.size.as_deref())?,
+                    loading,
                 },
                 dist: parse_distribution(cli.distribution.as_str())?,
                 output: PathBuf::from(cli.output),
             },
             Command::Slc(options) => Self::Slc {
-                indices: UnboundRange::parse(options.indices.as_str())?,
+                indices: Selection::parse(options.indices.as_str())?,
                 file: FileDiv {
                     file_path: PathBuf::from(options.image),
-                    div: Div::parse(options.size.as_str())?,
+                    div: Div::parse_or_embedded(options.size.as_deref())?,
+                    loading,
                 },
                 dist: parse_distribution(cli.distribution.as_str())?,
                 output: PathBuf::from(cli.output),
@@ -140,7 +323,214 @@ impl ParsedCommand {
             Command::Rev(options) => Self::Rev {
                 file: FileDiv {
                     file_path: PathBuf::from(options.image),
-                    div: Div::parse(options.size.as_str())?,
+                    div: Div::parse_or_embedded(options.size.as_deref())?,
+                    loading,
+                },
+                dist: parse_distribution(cli.distribution.as_str())?,
+                output: PathBuf::from(cli.output),
+            },
+            Command::Seq(options) => Self::Seq {
+                sequence: Selection::parse(options.sequence.as_str())?,
+                file: FileDiv {
+                    file_path: PathBuf::from(options.image),
+                    div: Div::parse_or_embedded(options.size.as_deref())?,
+                    loading,
+                },
+                dist: parse_distribution(cli.distribution.as_str())?,
+                output: PathBuf::from(cli.output),
+            },
+            Command::PingPong(options) => Self::PingPong {
+                repeat_endpoints: options.repeat_endpoints,
+                file: FileDiv {
+                    file_path: PathBuf::from(options.image),
+                    div: Div::parse_or_embedded(options.size.as_deref())?,
+                    loading,
+                },
+                dist: parse_distribution(cli.distribution.as_str())?,
+                output: PathBuf::from(cli.output),
+            },
+            Command::Hold(options) => Self::Hold {
+                holds: options
+                    .holds
+                    .split(',')
+                    .map(|hold| hold.trim().parse().context("could not parse hold count"))
+                    .collect::<Result<Vec<usize>>>()?,
+                file: FileDiv {
+                    file_path: PathBuf::from(options.image),
+                    div: Div::parse_or_embedded(options.size.as_deref())?,
+                    loading,
+                },
+                dist: parse_distribution(cli.distribution.as_str())?,
+                output: PathBuf::from(cli.output),
+            },
+            Command::Zip(options) => Self::Zip {
+                layout: ZipLayout::parse(options.layout.as_str())?,
+                files: parse_files(options.images, options.sizes, options.default_size, loading)?,
+                dist: parse_distribution(cli.distribution.as_str())?,
+                output: PathBuf::from(cli.output),
+            },
+            Command::MirrorDirs(options) => Self::MirrorDirs {
+                from: parse_directions(options.from.as_str())?,
+                to: parse_directions(options.to.as_str())?,
+                file: FileDiv {
+                    file_path: PathBuf::from(options.image),
+                    div: Div::parse_or_embedded(options.size.as_deref())?,
+                    loading,
+                },
+                output: PathBuf::from(cli.output),
+            },
+            Command::Name(options) => Self::Name {
+                pattern: options.pattern,
+                file: FileDiv {
+                    file_path: PathBuf::from(options.image),
+                    div: Div::parse_or_embedded(options.size.as_deref())?,
+                    loading,
+                },
+                dist: parse_distribution(cli.distribution.as_str())?,
+                output: PathBuf::from(cli.output),
+            },
+            Command::Pivot(options) => {
+                if options.all.is_none() && options.markers.is_none() {
+                    bail!("expected a pivot for all sprites (`--all`), a marker layer (`--markers`), or both");
+                }
+
+                let colour = parse_colour(&options.colour)?;
+
+                Self::Pivot {
+                    all: options.all.as_deref().map(parse_anchor).transpose()?,
+                    markers: options
+                        .markers
+                        .map(|markers| (PathBuf::from(markers), colour)),
+                    file: FileDiv {
+                        file_path: PathBuf::from(options.image),
+                        div: Div::parse_or_embedded(options.size.as_deref())?,
+                        loading,
+                    },
+                    output: PathBuf::from(cli.output),
+                }
+            }
+            Command::Boxes(options) => Self::Boxes {
+                markers: PathBuf::from(options.markers),
+                hurtbox: parse_colour(&options.hurtbox)?,
+                hitbox: parse_colour(&options.hitbox)?,
+                file: FileDiv {
+                    file_path: PathBuf::from(options.image),
+                    div: Div::parse_or_embedded(options.size.as_deref())?,
+                    loading,
+                },
+                output: PathBuf::from(cli.output),
+            },
+            Command::Mask(options) => Self::Mask {
+                threshold: options.threshold,
+                blob: options.blob.map(PathBuf::from),
+                file: FileDiv {
+                    file_path: PathBuf::from(options.image),
+                    div: Div::parse_or_embedded(options.size.as_deref())?,
+                    loading,
+                },
+                output: PathBuf::from(cli.output),
+            },
+            Command::Outline(options) => Self::Outline {
+                threshold: options.threshold,
+                max_vertices: options.max_vertices,
+                convex: options.convex,
+                triangulate: options.triangulate,
+                file: FileDiv {
+                    file_path: PathBuf::from(options.image),
+                    div: Div::parse_or_embedded(options.size.as_deref())?,
+                    loading,
+                },
+                output: PathBuf::from(cli.output),
+            },
+            Command::NineSlice(options) => Self::NineSlice {
+                borders: options
+                    .borders
+                    .as_deref()
+                    .map(str::parse)
+                    .transpose()
+                    .context("could not parse nine-slice borders")?,
+                to: match options.to.as_deref().map(Div::parse).transpose()? {
+                    Some(Div::Sprite(size)) => Some(size),
+                    Some(_) => bail!("nine-slice size should be a size in pixels, e.g. `200x64`"),
+                    None => None,
+                },
+                mode: if options.tile {
+                    SliceMode::Tile
+                } else {
+                    SliceMode::Stretch
+                },
+                file: FileDiv {
+                    file_path: PathBuf::from(options.image),
+                    div: Div::parse_or_embedded(options.size.as_deref())?,
+                    loading,
+                },
+                output: PathBuf::from(cli.output),
+            },
+            Command::Preview(options) => {
+                let mut preview = sheety::PreviewOptions {
+                    scale: options.scale,
+                    labels: !options.no_labels,
+                    label_scale: options.label_scale,
+                    ..Default::default()
+                };
+
+                preview.grid = (!options.no_grid)
+                    .then(|| parse_colour(&options.colour))
+                    .transpose()?;
+
+                if options.no_checkerboard {
+                    preview.checkerboard = None;
+                }
+
+                Self::Preview {
+                    options: preview,
+                    file: FileDiv {
+                        file_path: PathBuf::from(options.image),
+                        div: Div::parse_or_embedded(options.size.as_deref())?,
+                        loading,
+                    },
+                    output: PathBuf::from(cli.output),
+                }
+            }
+            Command::Diff(options) => Self::Diff {
+                base: FileDiv {
+                    file_path: PathBuf::from(options.base),
+                    div: Div::parse_or_embedded(
+                        options.base_size.as_deref().or(options.size.as_deref()),
+                    )?,
+                    loading,
+                },
+                colour: parse_colour(&options.colour)?,
+                file: FileDiv {
+                    file_path: PathBuf::from(options.image),
+                    div: Div::parse_or_embedded(options.size.as_deref())?,
+                    loading,
+                },
+                output: PathBuf::from(cli.output),
+            },
+            Command::Lint(options) => Self::Lint {
+                options: sheety::LintOptions {
+                    threshold: options.threshold,
+                    min_pixels: options.min_pixels,
+                    bounds_tolerance: options.tolerance,
+                },
+                file: FileDiv {
+                    file_path: PathBuf::from(options.image),
+                    div: Div::parse_or_embedded(options.size.as_deref())?,
+                    // The remainder is reported as a warning instead.
+                    loading: Loading {
+                        allow_remainder: true,
+                        ..loading
+                    },
+                },
+            },
+            Command::Retime(options) => Self::Retime {
+                from: options.from,
+                to: options.to,
+                file: FileDiv {
+                    file_path: PathBuf::from(options.image),
+                    div: Div::parse_or_embedded(options.size.as_deref())?,
+                    loading,
                 },
                 dist: parse_distribution(cli.distribution.as_str())?,
                 output: PathBuf::from(cli.output),
@@ -152,21 +542,31 @@ impl ParsedCommand {
         match self {
             Self::Cat {
                 files,
+                fit,
                 dist,
                 output,
             } => {
-                let list: Result<Vec<UnorderedSpriteSheet>> = files
-                    .into_iter()
-                    .map(|f| {
-                        f.load()?
-                            .into_unordered()
-                            .context("could get sprites of sprite sheet")
-                    })
-                    .collect();
+                let mut list = Vec::new();
+                let mut tags = Vec::new();
+
+                for file in files {
+                    let (sprites, file_tags) = file.load_sprites_with_tags()?;
+                    let offset: usize = list.iter().map(UnorderedSpriteSheet::len).sum();
+
+                    tags.extend(file_tags.into_iter().map(|tag| AnimationTag {
+                        frames: tag.frames.start + offset..tag.frames.end + offset,
+                        ..tag
+                    }));
+                    list.push(sprites);
+                }
+
+                if let Some(fit) = fit {
+                    fit.apply(&mut list);
+                }
 
-                SpriteSheet::concat(list?.into_iter(), dist)
+                SpriteSheet::concat(list.into_iter(), dist)
                     .context("could not concatenate sprite sheets")?
-                    .save(output)
+                    .save_with_tags(output, &tags)
                     .context("could not save file to disk")?;
             }
             Self::Del {
@@ -175,24 +575,20 @@ impl ParsedCommand {
                 dist,
                 output,
             } => {
-                let mut sheet = file
-                    .load()
-                    .context("could not load sprite sheet")?
-                    .into_unordered()
-                    .context("could not get sprites from sprite sheet")?;
+                let (sheet, tags) = file.load_sprites_with_tags()?;
 
-                let len = sheet.len();
-
-                for i in indices.into_range(len).rev() {
-                    if i >= sheet.len() {
-                        bail!("specified del index/range `{i}` is out of bounds (max: `{len}`)",);
-                    }
+                let mask = indices.resolve_mask(&frame_names(&sheet))?;
+                let origins: Vec<usize> = (0..mask.len()).filter(|i| !mask[*i]).collect();
 
-                    sheet.inner_mut().remove(i);
-                }
+                let sheet = UnorderedSpriteSheet::new(
+                    std::iter::zip(sheet, mask)
+                        .filter_map(|(sprite, deleted)| (!deleted).then_some(sprite))
+                        .collect(),
+                )
+                .context("could not make a sprite sheet out of the remaining sprites")?;
 
                 SpriteSheet::from_unordered(sheet, dist)
-                    .save(output)
+                    .save_with_tags(output, &remap_tags(&tags, &origins))
                     .context("could not save file to disk")?;
             }
             Self::Slc {
@@ -201,45 +597,455 @@ impl ParsedCommand {
                 dist,
                 output,
             } => {
-                let mut sheet = file
-                    .load()
-                    .context("could not load sprite sheet")?
-                    .into_unordered()
-                    .context("could not get sprites from sprite sheet")?;
+                let (sheet, tags) = file.load_sprites_with_tags()?;
+
+                let mask = indices.resolve_mask(&frame_names(&sheet))?;
+                let origins: Vec<usize> = (0..mask.len()).filter(|i| mask[*i]).collect();
+
+                let sheet = UnorderedSpriteSheet::new(
+                    std::iter::zip(sheet, mask)
+                        .filter_map(|(sprite, kept)| kept.then_some(sprite))
+                        .collect(),
+                )
+                .context("could not make a sprite sheet out of the sliced sprites")?;
 
+                SpriteSheet::from_unordered(sheet, dist)
+                    .save_with_tags(output, &remap_tags(&tags, &origins))
+                    .context("could not save file to disk")?;
+            }
+            Self::Rev { file, dist, output } => {
+                let (mut sheet, tags) = file.load_sprites_with_tags()?;
+                let origins: Vec<usize> = (0..sheet.len()).rev().collect();
+
+                sheet.inner_mut().reverse();
+
+                SpriteSheet::from_unordered(sheet, dist)
+                    .save_with_tags(output, &remap_tags(&tags, &origins))
+                    .context("could not save file to disk")?;
+            }
+            Self::Seq {
+                sequence,
+                file,
+                dist,
+                output,
+            } => {
+                let (sheet, tags) = file.load_sprites_with_tags()?;
+                let origins = sequence.resolve(&frame_names(&sheet))?;
+
+                let sheet = UnorderedSpriteSheet::new(
+                    origins.iter().map(|i| sheet.inner()[*i].clone()).collect(),
+                )
+                .context("could not make a sprite sheet out of the sequence")?;
+
+                SpriteSheet::from_unordered(sheet, dist)
+                    .save_with_tags(output, &remap_tags(&tags, &origins))
+                    .context("could not save file to disk")?;
+            }
+            Self::PingPong {
+                repeat_endpoints,
+                file,
+                dist,
+                output,
+            } => {
+                let (mut sheet, tags) = file.load_sprites_with_tags()?;
                 let len = sheet.len();
+                let origins: Vec<usize> = match repeat_endpoints {
+                    true => (0..len).chain((0..len).rev()).collect(),
+                    false => (0..len).chain((1..len.saturating_sub(1)).rev()).collect(),
+                };
+
+                sheet.ping_pong(repeat_endpoints);
+
+                SpriteSheet::from_unordered(sheet, dist)
+                    .save_with_tags(output, &remap_tags(&tags, &origins))
+                    .context("could not save file to disk")?;
+            }
+            Self::Hold {
+                holds,
+                file,
+                dist,
+                output,
+            } => {
+                let (mut sheet, tags) = file.load_sprites_with_tags()?;
+
+                let holds = match holds[..] {
+                    [hold] => vec![hold; sheet.len()],
+                    _ => holds,
+                };
+
+                sheet.hold(&holds).context("could not hold sprites")?;
+
+                let origins: Vec<usize> = (0..holds.len())
+                    .flat_map(|i| std::iter::repeat_n(i, holds[i]))
+                    .collect();
+
+                SpriteSheet::from_unordered(sheet, dist)
+                    .save_with_tags(output, &remap_tags(&tags, &origins))
+                    .context("could not save file to disk")?;
+            }
+            Self::Retime {
+                from,
+                to,
+                file,
+                dist,
+                output,
+            } => {
+                let (mut sheet, tags) = file.load_sprites_with_tags()?;
+
+                let origins = sheet
+                    .retime_origins(from, to)
+                    .context("could not retime sprites")?;
+
+                sheet.retime(from, to).context("could not retime sprites")?;
+
+                SpriteSheet::from_unordered(sheet, dist)
+                    .save_with_tags(output, &remap_tags(&tags, &origins))
+                    .context("could not save file to disk")?;
+            }
+            Self::Zip {
+                layout,
+                files,
+                dist,
+                output,
+            } => {
+                let (list, tags): (Vec<UnorderedSpriteSheet>, Vec<Vec<AnimationTag>>) = files
+                    .into_iter()
+                    .map(|f| f.load_sprites_with_tags())
+                    .collect::<Result<Vec<_>>>()?
+                    .into_iter()
+                    .unzip();
 
-                let indices = indices.into_range(len);
+                // The sheet each frame of the zipped sheet comes from, and its frame in that sheet.
+                let lens: Vec<usize> = list.iter().map(UnorderedSpriteSheet::len).collect();
+                let mut sources = Vec::new();
 
-                for i in (indices.end..len).rev() {
-                    if i >= sheet.len() {
-                        bail!("specified slc index/range `{i}` is out of bounds (max: `{len}`)");
+                match layout {
+                    ZipLayout::Lines => {
+                        for (i, len) in lens.iter().enumerate() {
+                            sources.extend((0..*len).map(|frame| (i, frame)));
+                        }
                     }
+                    ZipLayout::Columns | ZipLayout::Interleave => {
+                        for frame in 0..lens.iter().copied().max().unwrap_or(0) {
+                            for (i, len) in lens.iter().enumerate() {
+                                if frame < *len {
+                                    sources.push((i, frame));
+                                }
+                            }
+                        }
+                    }
+                }
 
-                    sheet.inner_mut().remove(i);
+                let mut zipped_tags = Vec::new();
+
+                for (i, tags) in tags.iter().enumerate() {
+                    let origins: Vec<Option<usize>> = sources
+                        .iter()
+                        .map(|(sheet, frame)| (*sheet == i).then_some(*frame))
+                        .collect();
+
+                    zipped_tags.extend(
+                        tags.iter()
+                            .filter_map(|tag| tag.remap(origins.iter().copied())),
+                    );
                 }
 
-                for i in (0..indices.start).rev() {
-                    sheet.inner_mut().remove(i);
+                let sheet = match layout {
+                    ZipLayout::Lines => SpriteSheet::zip_lines(list.into_iter()),
+                    ZipLayout::Columns | ZipLayout::Interleave => {
+                        SpriteSheet::zip_columns(list.into_iter())
+                    }
+                }
+                .context("could not zip sprite sheets")?;
+
+                match layout {
+                    ZipLayout::Interleave => SpriteSheet::from_unordered(
+                        sheet
+                            .into_unordered()
+                            .context("could get sprites of zipped sprite sheet")?,
+                        dist,
+                    ),
+                    ZipLayout::Lines | ZipLayout::Columns => sheet,
+                }
+                .save_with_tags(output, &zipped_tags)
+                .context("could not save file to disk")?;
+            }
+            Self::MirrorDirs {
+                from,
+                to,
+                file,
+                output,
+            } => {
+                let (sheet, tags) = file.load_with_tags()?;
+
+                // The frame of each non-empty cell, line after line, like the frames of the tags.
+                let mut count = 0;
+                let frames: Vec<Option<usize>> = sheet
+                    .cells()
+                    .map(|cell| {
+                        (!cell.is_empty()).then(|| {
+                            count += 1;
+                            count - 1
+                        })
+                    })
+                    .collect();
+                let width = sheet.size().0;
+
+                let sheet = DirectionalSheet::new(sheet, from.clone())
+                    .context("could not assign directions to the lines of the sprite sheet")?
+                    .mirror_missing(&to)
+                    .context("could not mirror directions")?;
+
+                // Mirrored lines come from the line of the mirrored direction.
+                let origins: Vec<usize> = to
+                    .iter()
+                    .filter_map(|direction| {
+                        from.iter()
+                            .position(|d| d == direction)
+                            .or_else(|| from.iter().position(|d| *d == direction.mirrored()))
+                    })
+                    .flat_map(|line| frames[line * width..(line + 1) * width].iter().flatten())
+                    .copied()
+                    .collect();
+
+                sheet
+                    .into_sheet()
+                    .save_with_tags(output, &remap_tags(&tags, &origins))
+                    .context("could not save file to disk")?;
+            }
+            Self::Name {
+                pattern,
+                file,
+                dist,
+                output,
+            } => {
+                let (mut sheet, tags) = file.load_sprites_with_tags()?;
+
+                for (i, sprite) in sheet.inner_mut().iter_mut().enumerate() {
+                    sprite.set_name(Some(&expand_name(&pattern, i)));
                 }
 
                 SpriteSheet::from_unordered(sheet, dist)
+                    .save_with_tags(output, &tags)
+                    .context("could not save file to disk")?;
+            }
+            Self::Pivot {
+                all,
+                markers,
+                file,
+                output,
+            } => {
+                // Marker layers are drawn over a transparent background.
+                let loading = Loading {
+                    allow_remainder: file.loading.allow_remainder,
+                    streaming: file.loading.streaming,
+                    ..Default::default()
+                };
+                let (mut sheet, tags) = file.load_with_tags()?;
+
+                if let Some(anchor) = all {
+                    for cell in sheet.cells_mut() {
+                        if let SpriteCell::Sprite(sprite) = cell {
+                            sprite.set_pivot_anchor(anchor);
+                        }
+                    }
+                }
+
+                if let Some((markers, colour)) = markers {
+                    let markers = FileDiv {
+                        file_path: markers,
+                        div: Div::Cells(sheet.size()),
+                        loading,
+                    }
+                    .load()?;
+
+                    sheet
+                        .set_pivots_from_markers(&markers, colour)
+                        .context("could not read pivots from the marker layer")?;
+                }
+
+                sheet
+                    .save_with_tags(output, &tags)
+                    .context("could not save file to disk")?;
+            }
+            Self::Boxes {
+                markers,
+                hurtbox,
+                hitbox,
+                file,
+                output,
+            } => {
+                // Marker layers are drawn over a transparent background.
+                let loading = Loading {
+                    allow_remainder: file.loading.allow_remainder,
+                    streaming: file.loading.streaming,
+                    ..Default::default()
+                };
+                let (mut sheet, tags) = file.load_with_tags()?;
+
+                let markers = FileDiv {
+                    file_path: markers,
+                    div: Div::Cells(sheet.size()),
+                    loading,
+                }
+                .load()?;
+
+                sheet
+                    .set_boxes_from_markers(&markers, hurtbox, hitbox)
+                    .context("could not read boxes from the marker layer")?;
+
+                sheet
+                    .save_with_tags(output, &tags)
+                    .context("could not save file to disk")?;
+            }
+            Self::Mask {
+                threshold,
+                blob,
+                file,
+                output,
+            } => {
+                let sheet = file.load()?;
+
+                if let Some(blob) = blob {
+                    sheet
+                        .save_masks(blob, threshold)
+                        .context("could not save masks to disk")?;
+                }
+
+                sheet
+                    .mask_sheet(threshold)
                     .save(output)
                     .context("could not save file to disk")?;
             }
-            Self::Rev { file, dist, output } => {
-                let mut sheet = file
-                    .load()
-                    .context("could not load sprite sheet")?
-                    .into_unordered()
-                    .context("could not get sprites from sprite sheet")?;
+            Self::Outline {
+                threshold,
+                max_vertices,
+                convex,
+                triangulate,
+                file,
+                output,
+            } => {
+                let (mut sheet, tags) = file.load_with_tags()?;
 
-                sheet.inner_mut().reverse();
+                for cell in sheet.cells_mut() {
+                    if let SpriteCell::Sprite(sprite) = cell {
+                        let mut outlines = sprite.trace_outlines(threshold, max_vertices);
 
-                SpriteSheet::from_unordered(sheet, dist)
+                        for outline in outlines.iter_mut() {
+                            if convex {
+                                *outline = outline.convex_hull();
+                            }
+
+                            if triangulate {
+                                outline.triangulate();
+                            }
+                        }
+
+                        sprite.set_outlines(outlines);
+                    }
+                }
+
+                sheet
+                    .save_with_tags(output, &tags)
+                    .context("could not save file to disk")?;
+            }
+            Self::NineSlice {
+                borders,
+                to,
+                mode,
+                file,
+                output,
+            } => {
+                let (mut sheet, tags) = file.load_with_tags()?;
+
+                if let Some(borders) = borders {
+                    for cell in sheet.cells_mut() {
+                        if let SpriteCell::Sprite(sprite) = cell {
+                            sprite.set_nine_slice(Some(borders));
+                        }
+                    }
+                }
+
+                if let Some(to) = to {
+                    let mut preview = SpriteSheet::new(sheet.size(), to);
+
+                    for (i, (cell, target)) in sheet.cells().zip(preview.cells_mut()).enumerate() {
+                        if let SpriteCell::Sprite(sprite) = cell {
+                            *target = SpriteCell::Sprite(
+                                sprite.nine_slice_to(to, mode).with_context(|| {
+                                    format!(
+                                        "could not resize the sprite of cell {i} as a nine-slice"
+                                    )
+                                })?,
+                            );
+                        }
+                    }
+
+                    sheet = preview;
+                }
+
+                sheet
+                    .save_with_tags(output, &tags)
+                    .context("could not save file to disk")?;
+            }
+            Self::Preview {
+                options,
+                file,
+                output,
+            } => {
+                let (sheet, metadata) = file.load_with_metadata()?;
+
+                sheet
+                    .preview(&options, metadata.as_ref())
+                    .context("could not draw the preview")?
                     .save(output)
                     .context("could not save file to disk")?;
             }
+            Self::Diff {
+                base,
+                colour,
+                file,
+                output,
+            } => {
+                let (base, sheet) = (base.load()?, file.load()?);
+                let diff = base
+                    .diff(&sheet)
+                    .context("could not compare the sprite sheets")?;
+
+                if diff.is_empty() {
+                    println!("no changes");
+                } else {
+                    print!("{diff}");
+                }
+
+                base.diff_image(&sheet, colour)
+                    .context("could not compare the sprite sheets")?
+                    .save(output)
+                    .context("could not save file to disk")?;
+            }
+            Self::Lint { options, file } => {
+                // Projects know their own grid, so only images can be sliced with the wrong size.
+                let image_size = image::image_dimensions(&file.file_path).ok();
+                let sheet = file.load()?;
+
+                let warnings: Vec<_> = image_size
+                    .and_then(|(width, height)| {
+                        sheet.lint_image_size((width as usize, height as usize))
+                    })
+                    .into_iter()
+                    .chain(sheet.lint(&options))
+                    .collect();
+
+                if warnings.is_empty() {
+                    println!("no warnings");
+                } else {
+                    for warning in warnings.iter() {
+                        println!("{warning}");
+                    }
+
+                    bail!("found {} warning(s)", warnings.len());
+                }
+            }
         }
 
         Ok(())
@@ -276,34 +1082,405 @@ fn parse_distribution(txt: &str) -> Result<Distribution> {
     })
 }
 
+/// Moves `tags` to the frames of a new sheet, `origins` giving the frame each new frame comes from.
+fn remap_tags(tags: &[AnimationTag], origins: &[usize]) -> Vec<AnimationTag> {
+    tags.iter()
+        .filter_map(|tag| tag.remap(origins.iter().copied().map(Some)))
+        .collect()
+}
+
+/// Gets the names of the sprites of `sheet`, to resolve [Selection]s.
+fn frame_names(sheet: &UnorderedSpriteSheet) -> Vec<Option<&str>> {
+    sheet.inner().iter().map(|sprite| sprite.name()).collect()
+}
+
+/// Replaces each run of `#` in `pattern` with `index`, padded with zeros to the length of the run.
+fn expand_name(pattern: &str, index: usize) -> String {
+    let mut name = String::new();
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '#' {
+            let mut width = 1;
+
+            while chars.next_if_eq(&'#').is_some() {
+                width += 1;
+            }
+
+            name.push_str(&format!("{index:0width$}"));
+        } else {
+            name.push(c);
+        }
+    }
+
+    name
+}
+
+/// Parses a colour from a hex code, e.g. `ff00ff`, or `ff00ff80` with an alpha channel.
+fn parse_colour(txt: &str) -> Result<Rgba<u8>> {
+    let hex = txt.trim_start_matches('#');
+
+    if !matches!(hex.len(), 6 | 8) {
+        bail!("colour `{txt}` should be a hex code such as `ff00ff`");
+    }
+
+    let channel = |i: usize| {
+        hex.get(i * 2..i * 2 + 2)
+            .and_then(|channel| u8::from_str_radix(channel, 16).ok())
+            .with_context(|| format!("could not parse colour `{txt}`"))
+    };
+
+    Ok(Rgba([
+        channel(0)?,
+        channel(1)?,
+        channel(2)?,
+        if hex.len() == 8 { channel(3)? } else { 255 },
+    ]))
+}
+
+fn parse_background(txt: &str) -> Result<Background> {
+    let (kind, value) = match txt.split_once(':') {
+        Some((kind, value)) => (kind, Some(value)),
+        None => (txt, None),
+    };
+    let number = |what: &str| {
+        value.map_or(Ok(0), |value| {
+            value
+                .parse()
+                .with_context(|| format!("could not parse background {what} `{value}`"))
+        })
+    };
+
+    Ok(match kind {
+        "alpha" => Background::Alpha(number("threshold")?),
+        "auto" => Background::Auto {
+            tolerance: number("tolerance")?,
+        },
+        colour => Background::Key {
+            colour: parse_colour(colour)?,
+            tolerance: number("tolerance")?,
+        },
+    })
+}
+
+fn parse_directions(txt: &str) -> Result<Vec<Direction>> {
+    txt.split(',')
+        .map(|direction| {
+            direction
+                .trim()
+                .parse()
+                .context("could not parse direction")
+        })
+        .collect()
+}
+
+fn parse_files(
+    images: Vec<String>,
+    sizes: Vec<String>,
+    default_size: String,
+    loading: Loading,
+) -> Result<Vec<FileDiv>> {
+    if sizes.is_empty() && default_size.is_empty() {
+        // no sizes given, they are read from the images
+        Ok(images
+            .into_iter()
+            .map(|f| FileDiv {
+                file_path: PathBuf::from(f),
+                div: Div::Embedded,
+                loading,
+            })
+            .collect())
+    } else if sizes.is_empty() {
+        // no sizes given and a default size given
+        images
+            .into_iter()
+            .map(|f| {
+                Ok(FileDiv {
+                    file_path: PathBuf::from(f),
+                    div: Div::parse(default_size.as_str())?,
+                    loading,
+                })
+            })
+            .collect()
+    } else if sizes.len() == images.len() {
+        // a size given for each sprite
+        std::iter::zip(images, sizes)
+            .map(|(img, size)| {
+                Ok(FileDiv {
+                    file_path: PathBuf::from(img),
+                    div: Div::parse(size.as_str())?,
+                    loading,
+                })
+            })
+            .collect()
+    } else {
+        bail!("size count should be the same as the image count, or there should be a default size and nothing else");
+    }
+}
+
+#[derive(Debug)]
+struct FitOptions {
+    fit: Fit,
+    target: FitTarget,
+}
+
+#[derive(Debug)]
+enum FitTarget {
+    Largest,
+    Smallest,
+    Size((usize, usize)),
+}
+
+impl FitOptions {
+    fn parse(txt: &str, size: Option<&str>) -> Result<Self> {
+        let (mode, anchor) = txt.split_once(':').unwrap_or((txt, "center"));
+        let anchor = parse_anchor(anchor)?;
+
+        let (fit, target) = match mode {
+            "pad" => (Fit::Anchor(anchor), FitTarget::Largest),
+            "crop" => (Fit::Anchor(anchor), FitTarget::Smallest),
+            "scale" => (Fit::Scale(anchor), FitTarget::Largest),
+            unknown => {
+                bail!("fit should be either 'pad', 'crop' or 'scale', unknown fit '{unknown}'")
+            }
+        };
+
+        let target = match size {
+            Some(size) => match Div::parse(size)? {
+                Div::Sprite(size) => FitTarget::Size(size),
+                _ => bail!("fit size should be a size in pixels, e.g. `64x64`"),
+            },
+            None => target,
+        };
+
+        Ok(Self { fit, target })
+    }
+
+    fn apply(self, list: &mut [UnorderedSpriteSheet]) {
+        let sizes = list.iter().map(|unordered| unordered.size());
+
+        let size = match self.target {
+            FitTarget::Largest => sizes.fold((0, 0), |acc, size| {
+                (usize::max(acc.0, size.0), usize::max(acc.1, size.1))
+            }),
+            FitTarget::Smallest => sizes.fold((usize::MAX, usize::MAX), |acc, size| {
+                (usize::min(acc.0, size.0), usize::min(acc.1, size.1))
+            }),
+            FitTarget::Size(size) => size,
+        };
+
+        for unordered in list.iter_mut() {
+            unordered.fit(size, self.fit);
+        }
+    }
+}
+
+fn parse_anchor(txt: &str) -> Result<Anchor> {
+    const PARSE_CONTEXT: &str = "could not parse anchor pivot";
+
+    Ok(match txt {
+        "top-left" => Anchor::TopLeft,
+        "top" => Anchor::Top,
+        "top-right" => Anchor::TopRight,
+        "left" => Anchor::Left,
+        "center" => Anchor::Center,
+        "right" => Anchor::Right,
+        "bottom-left" => Anchor::BottomLeft,
+        "bottom" => Anchor::Bottom,
+        "bottom-right" => Anchor::BottomRight,
+        pivot => match pivot.split_once(',') {
+            Some((x, y)) => Anchor::Pivot(
+                x.trim().parse().context(PARSE_CONTEXT)?,
+                y.trim().parse().context(PARSE_CONTEXT)?,
+            ),
+            None => bail!("unknown anchor '{pivot}'"),
+        },
+    })
+}
+
+#[derive(Debug)]
+enum ZipLayout {
+    Interleave,
+    Lines,
+    Columns,
+}
+
+impl ZipLayout {
+    fn parse(txt: &str) -> Result<Self> {
+        Ok(match txt {
+            "interleave" => Self::Interleave,
+            "lines" => Self::Lines,
+            "columns" => Self::Columns,
+            unknown => bail!(
+                "zip layout should be either 'interleave', 'lines' or 'columns', unknown layout '{unknown}'"
+            ),
+        })
+    }
+}
+
 #[derive(Debug)]
 struct FileDiv {
     file_path: PathBuf,
     div: Div,
+    loading: Loading,
+}
+
+/// How to load the sprite sheets given to a command.
+#[derive(Debug, Clone, Copy, Default)]
+struct Loading {
+    /// Whether images that aren't made of whole cells are loaded anyway, dropping the remaining pixels.
+    allow_remainder: bool,
+    /// Cells whose pixels are all background are emptied.
+    background: Option<Background>,
+    /// Whether background pixels are made fully transparent.
+    key_out: bool,
+    /// Whether PNG images are read one line of cells at a time instead of all at once.
+    streaming: bool,
 }
 
 impl FileDiv {
+    /// With `--streaming`, PNG images are loaded one line of cells at a time, so that very large sheets don't have
+    /// to fit in memory.
     fn load(self) -> Result<SpriteSheet> {
-        Ok(match self.div {
-            Div::Cells(div) => {
-                SpriteSheet::load_div(self.file_path, div).context("could not load sprite sheet")?
+        Ok(self.load_with_metadata()?.0)
+    }
+
+    /// Same as [FileDiv::load], along with the animation tags of the sheet, moved to its non-empty cells, line
+    /// after line, so that [SpriteSheet::save_with_tags] can save them back.
+    fn load_with_tags(self) -> Result<(SpriteSheet, Vec<AnimationTag>)> {
+        let (sheet, metadata) = self.load_with_metadata()?;
+
+        let Some(metadata) = metadata else {
+            return Ok((sheet, Vec::new()));
+        };
+
+        let mut frames = vec![None; sheet.cells().len()];
+
+        for (frame, i) in metadata.frames.iter().enumerate().rev() {
+            frames[*i] = Some(frame);
+        }
+
+        let origins: Vec<Option<usize>> = std::iter::zip(sheet.cells(), frames)
+            .filter_map(|(cell, frame)| (!cell.is_empty()).then_some(frame))
+            .collect();
+
+        let tags = metadata
+            .tags
+            .iter()
+            .filter_map(|tag| tag.remap(origins.iter().copied()))
+            .collect();
+
+        Ok((sheet, tags))
+    }
+
+    /// Loads the sprites of the sprite sheet, along with its animation tags. Sheets saved by sheety keep the order
+    /// of their frames, as well as fully transparent frames.
+    fn load_sprites_with_tags(self) -> Result<(UnorderedSpriteSheet, Vec<AnimationTag>)> {
+        match self.load_with_metadata()? {
+            (sheet, Some(metadata)) => metadata
+                .unordered(&sheet)
+                .map(|sprites| (sprites, metadata.tags)),
+            (sheet, None) => sheet.into_unordered().map(|sprites| (sprites, Vec::new())),
+        }
+        .context("could not get sprites from sprite sheet")
+    }
+
+    /// Empties the cells made of background only, and makes the background transparent with `--key-out`.
+    fn load_with_metadata(self) -> Result<(SpriteSheet, Option<SheetMetadata>)> {
+        let loading = self.loading;
+        let (mut sheet, metadata) = self.load_sheet()?;
+
+        match (loading.background, loading.key_out) {
+            (background, true) => {
+                sheet.key_out(background.unwrap_or(Background::Auto { tolerance: 0 }))
             }
-            Div::Sprite(size) => SpriteSheet::load_cell_size(self.file_path, size)
-                .context("could not load sprite sheet")?,
-            Div::Single => SpriteSheet::load_div(self.file_path, (1, 1))
-                .context("could not load sprite sheet")?,
-        })
+            (Some(background), false) => sheet.clear_background(background),
+            (None, false) => {}
+        }
+
+        Ok((sheet, metadata))
+    }
+
+    /// `.sheety` projects know their own grid, so their size is ignored.
+    fn load_sheet(self) -> Result<(SpriteSheet, Option<SheetMetadata>)> {
+        let extension = self.file_path.extension().unwrap_or_default();
+
+        if extension.eq_ignore_ascii_case("sheety") {
+            return SpriteSheet::load_project(&self.file_path)
+                .map(|(sheet, metadata)| (sheet, Some(metadata)))
+                .with_context(|| format!("could not load project {:?}", self.file_path));
+        }
+
+        let streaming = self.loading.streaming && extension.eq_ignore_ascii_case("png");
+        let remainder = match self.loading.allow_remainder {
+            true => Remainder::Allow,
+            false => Remainder::Reject,
+        };
+
+        let div = match self.div {
+            Div::Cells(div) => div,
+            Div::Single => (1, 1),
+            Div::Sprite(size) => {
+                return match streaming {
+                    true => SpriteSheet::load_streaming_cell_size(&self.file_path, size, remainder),
+                    false => Sprite::load(&self.file_path).and_then(|sprite| {
+                        SpriteSheet::try_from_image_cell_size(sprite, size, remainder)
+                    }),
+                }
+                .map(|sheet| (sheet, None))
+                .map_err(|error| load_error(error, &self.file_path))
+            }
+            Div::Embedded => {
+                return match streaming {
+                    true => SpriteSheet::load_streaming_with_metadata(&self.file_path),
+                    false => SpriteSheet::load_with_metadata(&self.file_path),
+                }
+                .map(|(sheet, metadata)| (sheet, Some(metadata)))
+                .with_context(|| {
+                    format!(
+                        "could not load sprite sheet {:?} without a size; give one with -s",
+                        self.file_path
+                    )
+                })
+            }
+        };
+
+        match streaming {
+            true => SpriteSheet::load_streaming_div(&self.file_path, div, remainder),
+            false => Sprite::load(&self.file_path)
+                .and_then(|sprite| SpriteSheet::try_from_image_div(sprite, div, remainder)),
+        }
+        .map(|sheet| (sheet, None))
+        .map_err(|error| load_error(error, &self.file_path))
     }
 }
 
+/// Adds the path of the sprite sheet to `error`, and how to load it anyway if it isn't made of whole cells.
+fn load_error(error: sheety::error::Error, file_path: &Path) -> anyhow::Error {
+    let hint = match error {
+        sheety::error::Error::IndivisibleSheet { .. } => {
+            "; check its size, or give --allow-remainder to drop the remaining pixels"
+        }
+        _ => "",
+    };
+
+    anyhow::Error::new(error).context(format!("could not load sprite sheet {file_path:?}{hint}"))
+}
+
 #[derive(Debug)]
 enum Div {
     Cells((usize, usize)),
     Sprite((usize, usize)),
     Single,
+    /// Read from the metadata sheety embeds in the images it saves.
+    Embedded,
 }
 
 impl Div {
+    fn parse_or_embedded(txt: Option<&str>) -> Result<Self> {
+        txt.map_or(Ok(Self::Embedded), Self::parse)
+    }
+
     fn parse(txt: &str) -> Result<Self> {
         const PARSE_CONTEXT: &str = "could not parse size";
 
@@ -325,40 +1502,3 @@ impl Div {
         }
     }
 }
-
-#[derive(Debug)]
-struct UnboundRange {
-    start: usize,
-    end: Option<usize>,
-}
-
-impl UnboundRange {
-    fn parse(txt: &str) -> Result<Self> {
-        const PARSE_CONTEXT: &str = "could not parse deletion range";
-
-        if let Some((min, max)) = txt.split_once('-') {
-            Ok(Self {
-                start: if min.is_empty() {
-                    0
-                } else {
-                    min.parse().context(PARSE_CONTEXT)?
-                },
-                end: if max.is_empty() {
-                    None
-                } else {
-                    Some(max.parse().context(PARSE_CONTEXT)?)
-                },
-            })
-        } else {
-            let both = txt.parse().context(PARSE_CONTEXT)?;
-            Ok(Self {
-                start: both,
-                end: Some(both + 1),
-            })
-        }
-    }
-
-    fn into_range(self, upper_bound: usize) -> Range<usize> {
-        self.start..self.end.unwrap_or(upper_bound)
-    }
-}
@@ -0,0 +1,21 @@
+use clap::Args;
+
+#[derive(Debug, Args)]
+pub(crate) struct MaskOptions {
+    /// Pixels are solid if their alpha is greater than the threshold, `0` makes every visible pixel solid
+    #[arg(short = 't', long = "threshold", default_value_t = 0)]
+    pub threshold: u8,
+
+    /// Also write the masks as a bit-packed binary blob into this file
+    #[arg(short = 'b', long = "blob")]
+    pub blob: Option<String>,
+
+    /// Image file of the target sprite sheet
+    #[arg(short = 'i', long = "image")]
+    pub image: String,
+
+    /// Size of the target sprite sheet, e.g. `40x50` for pixel size, `3-3` for cell count,
+    /// `single` for a single image; `10` is the same as `10x10`; can be omitted for sheets saved by sheety
+    #[arg(short = 's', long = "size")]
+    pub size: Option<String>,
+}
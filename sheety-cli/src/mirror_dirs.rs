@@ -0,0 +1,21 @@
+use clap::Args;
+
+#[derive(Debug, Args)]
+pub(crate) struct MirrorDirsOptions {
+    /// The direction of each line of the sprite sheet, separated by commas (e.g. `E,NE,N,SE,S`)
+    #[arg(short = 'f', long = "from")]
+    pub from: String,
+
+    /// The directions to output, one per line, missing ones are mirrored from the opposite direction
+    #[arg(short = 't', long = "to", default_value_t = String::from("E,NE,N,NW,W,SW,S,SE"))]
+    pub to: String,
+
+    /// Image file of the target sprite sheet
+    #[arg(short = 'i', long = "image")]
+    pub image: String,
+
+    /// Size of the target sprite sheet, e.g. `40x50` for pixel size, `3-3` for cell count,
+    /// `single` for a single image; `10` is the same as `10x10`; can be omitted for sheets saved by sheety
+    #[arg(short = 's', long = "size")]
+    pub size: Option<String>,
+}
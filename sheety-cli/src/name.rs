@@ -0,0 +1,17 @@
+use clap::Args;
+
+#[derive(Debug, Args)]
+pub(crate) struct NameOptions {
+    /// The name of the sprites, where each run of `#` is replaced by the index of the sprite, padded with zeros
+    /// (e.g. `run_##` names the sprites `run_00`, `run_01`, ...)
+    pub pattern: String,
+
+    /// Image file of the target sprite sheet
+    #[arg(short = 'i', long = "image")]
+    pub image: String,
+
+    /// Size of the target sprite sheet, e.g. `40x50` for pixel size, `3-3` for cell count,
+    /// `single` for a single image; `10` is the same as `10x10`; can be omitted for sheets saved by sheety
+    #[arg(short = 's', long = "size")]
+    pub size: Option<String>,
+}
@@ -0,0 +1,27 @@
+use clap::Args;
+
+#[derive(Debug, Args)]
+pub(crate) struct NineSliceOptions {
+    /// The nine-slice borders of every sprite, in pixels, as `left,top,right,bottom` (e.g. `8,8,8,12`), or a single
+    /// number for the same border on every side; can be omitted for sheets saved by sheety with borders
+    #[arg(short = 'b', long = "borders")]
+    pub borders: Option<String>,
+
+    /// Size to resize every sprite to as a nine-slice, in pixels (e.g. `200x64`), to preview it; without it, the
+    /// borders are only saved
+    #[arg(short = 't', long = "to")]
+    pub to: Option<String>,
+
+    /// Repeat the edges and the centre instead of stretching them
+    #[arg(long = "tile")]
+    pub tile: bool,
+
+    /// Image file of the target sprite sheet
+    #[arg(short = 'i', long = "image")]
+    pub image: String,
+
+    /// Size of the target sprite sheet, e.g. `40x50` for pixel size, `3-3` for cell count,
+    /// `single` for a single image; `10` is the same as `10x10`; can be omitted for sheets saved by sheety
+    #[arg(short = 's', long = "size")]
+    pub size: Option<String>,
+}
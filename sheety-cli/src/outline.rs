@@ -0,0 +1,29 @@
+use clap::Args;
+
+#[derive(Debug, Args)]
+pub(crate) struct OutlineOptions {
+    /// Pixels are solid if their alpha is greater than the threshold, `0` makes every visible pixel solid
+    #[arg(short = 't', long = "threshold", default_value_t = 0)]
+    pub threshold: u8,
+
+    /// The maximum amount of vertices of each outline
+    #[arg(short = 'n', long = "max-vertices", default_value_t = 8)]
+    pub max_vertices: usize,
+
+    /// Use the convex hull of each outline
+    #[arg(long = "convex")]
+    pub convex: bool,
+
+    /// Also cut each outline into triangles
+    #[arg(long = "triangulate")]
+    pub triangulate: bool,
+
+    /// Image file of the target sprite sheet
+    #[arg(short = 'i', long = "image")]
+    pub image: String,
+
+    /// Size of the target sprite sheet, e.g. `40x50` for pixel size, `3-3` for cell count,
+    /// `single` for a single image; `10` is the same as `10x10`; can be omitted for sheets saved by sheety
+    #[arg(short = 's', long = "size")]
+    pub size: Option<String>,
+}
@@ -0,0 +1,25 @@
+use clap::Args;
+
+#[derive(Debug, Args)]
+pub(crate) struct PivotOptions {
+    /// The pivot of every sprite, as an anchor (e.g. `bottom`, or `0.5,1.0` relative to the size of the sprites)
+    #[arg(short = 'a', long = "all")]
+    pub all: Option<String>,
+
+    /// Image file of a marker layer, aligned cell for cell with the sprite sheet, where pivots are painted
+    #[arg(short = 'm', long = "markers")]
+    pub markers: Option<String>,
+
+    /// Colour of the pivots in the marker layer, as a hex code (e.g. `ff00ff`)
+    #[arg(short = 'c', long = "colour", default_value_t = String::from("ff00ff"))]
+    pub colour: String,
+
+    /// Image file of the target sprite sheet
+    #[arg(short = 'i', long = "image")]
+    pub image: String,
+
+    /// Size of the target sprite sheet, e.g. `40x50` for pixel size, `3-3` for cell count,
+    /// `single` for a single image; `10` is the same as `10x10`; can be omitted for sheets saved by sheety
+    #[arg(short = 's', long = "size")]
+    pub size: Option<String>,
+}
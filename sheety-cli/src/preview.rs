@@ -0,0 +1,37 @@
+use clap::Args;
+
+#[derive(Debug, Args)]
+pub(crate) struct PreviewOptions {
+    /// Scale of the preview, e.g. `4` to draw each pixel of the sheet as 4x4 pixels
+    #[arg(short = 'x', long = "scale", default_value_t = 1)]
+    pub scale: u32,
+
+    /// Scale of the labels, whose characters are 3x5 pixels
+    #[arg(long = "label-scale", default_value_t = 2)]
+    pub label_scale: u32,
+
+    /// Colour of the lines between cells, as a hex code (e.g. `ff00ff`)
+    #[arg(short = 'c', long = "colour", default_value_t = String::from("ff00ff"))]
+    pub colour: String,
+
+    /// Don't draw lines between cells
+    #[arg(long = "no-grid")]
+    pub no_grid: bool,
+
+    /// Keep transparent pixels transparent, instead of drawing a checkerboard behind them
+    #[arg(long = "no-checkerboard")]
+    pub no_checkerboard: bool,
+
+    /// Don't write the index and the name of each sprite
+    #[arg(long = "no-labels")]
+    pub no_labels: bool,
+
+    /// Image file of the target sprite sheet
+    #[arg(short = 'i', long = "image")]
+    pub image: String,
+
+    /// Size of the target sprite sheet, e.g. `40x50` for pixel size, `3-3` for cell count,
+    /// `single` for a single image; `10` is the same as `10x10`; can be omitted for sheets saved by sheety
+    #[arg(short = 's', long = "size")]
+    pub size: Option<String>,
+}
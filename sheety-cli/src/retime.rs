@@ -0,0 +1,21 @@
+use clap::Args;
+
+#[derive(Debug, Args)]
+pub(crate) struct RetimeOptions {
+    /// The frame rate the animation was made for
+    #[arg(short = 'f', long = "from")]
+    pub from: f32,
+
+    /// The frame rate to resample the animation to
+    #[arg(short = 't', long = "to")]
+    pub to: f32,
+
+    /// Image file of the target sprite sheet
+    #[arg(short = 'i', long = "image")]
+    pub image: String,
+
+    /// Size of the target sprite sheet, e.g. `40x50` for pixel size, `3-3` for cell count,
+    /// `single` for a single image; `10` is the same as `10x10`; can be omitted for sheets saved by sheety
+    #[arg(short = 's', long = "size")]
+    pub size: Option<String>,
+}
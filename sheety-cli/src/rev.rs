@@ -7,7 +7,7 @@ pub(crate) struct RevOptions {
     pub image: String,
 
     /// Size of the target sprite sheet, e.g. `40x50` for pixel size, `3-3` for cell count,
-    /// `single` for a single image; `10` is the same as `10x10`
+    /// `single` for a single image; `10` is the same as `10x10`; can be omitted for sheets saved by sheety
     #[arg(short = 's', long = "size")]
-    pub size: String,
+    pub size: Option<String>,
 }
@@ -0,0 +1,466 @@
+use std::{iter::Peekable, str::Chars};
+
+use anyhow::{bail, Context, Result};
+
+/// How many times more frames than the sheet has a selection can resolve into, at most.
+const MAX_SELECTION_FACTOR: usize = 1024;
+
+/// A list of frames, parsed from a selection such as `0-3,7,5x2,rev(8-12),^2-`.
+///
+/// Grammar:
+/// - `n`: a single frame, `^n` counts from the end (`^1` is the last frame), `@name` is the first frame named `name`;
+///   negative indices are written `^n` rather than `-n`, which is a range from the first frame
+/// - names end before whitespace, any of `,()-:`, or an `x` followed by a digit, so that `@run_03x2` repeats
+///   `run_03`; other names are quoted, e.g. `@"walk-01"`
+/// - `a-b`: frames `a` through `b`, `b` excluded; either bound can be omitted
+/// - `a-b:s`: same, but only keeping every `s`th frame
+/// - `item x n`: repeats an item `n` times, e.g. `5x2` or `(0-3)x2`; a repeat can't make the selection more than
+///   1024 times as long as the sheet
+/// - `rev(list)`: reverses a list, `(list)` groups one
+/// - items are separated with `,`
+#[derive(Debug)]
+pub(crate) struct Selection {
+    items: Vec<Item>,
+}
+
+#[derive(Debug)]
+enum Item {
+    Single(Index),
+    Range {
+        start: Option<Index>,
+        end: Option<Index>,
+        step: usize,
+    },
+    Group(Selection),
+    Rev(Selection),
+    Repeat(Box<Item>, usize),
+}
+
+#[derive(Debug, Clone)]
+enum Index {
+    FromStart(usize),
+    FromEnd(usize),
+    Named(String),
+}
+
+impl Selection {
+    pub(crate) fn parse(txt: &str) -> Result<Self> {
+        let mut parser = Parser {
+            chars: txt.chars().peekable(),
+        };
+
+        let selection = parser
+            .list()
+            .with_context(|| format!("could not parse selection `{txt}`"))?;
+
+        if let Some(c) = parser.peek() {
+            bail!("could not parse selection `{txt}`: unexpected character '{c}'");
+        }
+
+        Ok(selection)
+    }
+
+    /// Resolves the selection into a list of frame indices, for a sheet whose frames have the given `names`.
+    /// The list keeps the order and the duplicates of the selection.
+    pub(crate) fn resolve(&self, names: &[Option<&str>]) -> Result<Vec<usize>> {
+        let mut frames = Vec::new();
+
+        for item in self.items.iter() {
+            item.resolve(names, &mut frames)?;
+        }
+
+        Ok(frames)
+    }
+
+    /// Resolves the selection into a mask of booleans, `true` for selected frames, for a sheet whose frames have
+    /// the given `names`.
+    pub(crate) fn resolve_mask(&self, names: &[Option<&str>]) -> Result<Vec<bool>> {
+        let mut mask = vec![false; names.len()];
+
+        for i in self.resolve(names)? {
+            mask[i] = true;
+        }
+
+        Ok(mask)
+    }
+}
+
+impl Item {
+    fn resolve(&self, names: &[Option<&str>], frames: &mut Vec<usize>) -> Result<()> {
+        let len = names.len();
+
+        match self {
+            Self::Single(index) => {
+                let i = index.resolve(names)?;
+
+                if i >= len {
+                    bail!("specified frame `{i}` is out of bounds (max: `{len}`)");
+                }
+
+                frames.push(i);
+            }
+            Self::Range { start, end, step } => {
+                let start = start.as_ref().map_or(Ok(0), |i| i.resolve(names))?;
+                let end = end.as_ref().map_or(Ok(len), |i| i.resolve(names))?;
+
+                if start > len || end > len {
+                    bail!("specified range `{start}-{end}` is out of bounds (max: `{len}`)");
+                }
+
+                if start > end {
+                    bail!("specified range `{start}-{end}` ends before it starts");
+                }
+
+                frames.extend((start..end).step_by(*step));
+            }
+            Self::Group(selection) => frames.extend(selection.resolve(names)?),
+            Self::Rev(selection) => frames.extend(selection.resolve(names)?.into_iter().rev()),
+            Self::Repeat(item, count) => {
+                let mut repeated = Vec::new();
+                item.resolve(names, &mut repeated)?;
+
+                let max = len.max(1) * MAX_SELECTION_FACTOR;
+                let total = repeated
+                    .len()
+                    .checked_mul(*count)
+                    .and_then(|total| total.checked_add(frames.len()))
+                    .filter(|&total| total <= max);
+
+                if total.is_none() {
+                    bail!("specified repeat `x{count}` selects more than `{max}` frames");
+                }
+
+                for _ in 0..*count {
+                    frames.extend_from_slice(&repeated);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Index {
+    fn resolve(&self, names: &[Option<&str>]) -> Result<usize> {
+        let len = names.len();
+
+        match self {
+            Self::FromStart(i) => Ok(*i),
+            Self::FromEnd(i) => len
+                .checked_sub(*i)
+                .with_context(|| format!("specified frame `^{i}` is out of bounds (max: `{len}`)")),
+            Self::Named(name) => names
+                .iter()
+                .position(|n| *n == Some(name.as_str()))
+                .with_context(|| format!("no frame is named `{name}`")),
+        }
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.peek().copied()
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.chars.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.chars.next();
+                Ok(())
+            }
+            Some(c) => bail!("expected '{expected}', found '{c}'"),
+            None => bail!("expected '{expected}', found the end of the selection"),
+        }
+    }
+
+    fn list(&mut self) -> Result<Selection> {
+        let mut items = vec![self.item()?];
+
+        while self.eat(',') {
+            items.push(self.item()?);
+        }
+
+        Ok(Selection { items })
+    }
+
+    fn item(&mut self) -> Result<Item> {
+        let mut item = self.atom()?;
+
+        while self.eat('x') {
+            item = Item::Repeat(Box::new(item), self.number()?);
+        }
+
+        Ok(item)
+    }
+
+    fn atom(&mut self) -> Result<Item> {
+        if self.eat('(') {
+            let selection = self.list()?;
+            self.expect(')')?;
+            return Ok(Item::Group(selection));
+        }
+
+        if self.eat('r') {
+            for c in ['e', 'v', '('] {
+                self.expect(c)?;
+            }
+
+            let selection = self.list()?;
+            self.expect(')')?;
+            return Ok(Item::Rev(selection));
+        }
+
+        let start = self.index()?;
+
+        if !self.eat('-') {
+            return match start {
+                Some(index) => Ok(Item::Single(index)),
+                None => match self.peek() {
+                    Some(c) => bail!("expected a frame or a range, found '{c}'"),
+                    None => bail!("expected a frame or a range, found the end of the selection"),
+                },
+            };
+        }
+
+        let end = self.index()?;
+
+        let step = if self.eat(':') { self.number()? } else { 1 };
+
+        if step == 0 {
+            bail!("range step should be greater than 0");
+        }
+
+        Ok(Item::Range { start, end, step })
+    }
+
+    fn index(&mut self) -> Result<Option<Index>> {
+        if self.eat('^') {
+            Ok(Some(Index::FromEnd(self.number()?)))
+        } else if self.eat('@') {
+            Ok(Some(Index::Named(self.name()?)))
+        } else if self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            Ok(Some(Index::FromStart(self.number()?)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Reads a frame name, either quoted, or up to whitespace, the next character that is part of the grammar, or
+    /// a repeat.
+    fn name(&mut self) -> Result<String> {
+        let mut name = String::new();
+
+        if self.chars.next_if_eq(&'"').is_some() {
+            loop {
+                match self.chars.next() {
+                    Some('"') => break,
+                    Some(c) => name.push(c),
+                    None => bail!("expected '\"', found the end of the selection"),
+                }
+            }
+
+            if name.is_empty() {
+                bail!("expected a name, found an empty quoted name");
+            }
+
+            return Ok(name);
+        }
+
+        while let Some(&c) = self.chars.peek() {
+            if c.is_whitespace() || ",()-:".contains(c) || (c == 'x' && self.repeat_ahead()) {
+                break;
+            }
+
+            name.push(c);
+            self.chars.next();
+        }
+
+        if name.is_empty() {
+            match self.peek() {
+                Some(c) => bail!("expected a name, found '{c}'"),
+                None => bail!("expected a name, found the end of the selection"),
+            }
+        }
+
+        Ok(name)
+    }
+
+    /// Returns `true` if the next characters are an `x` followed by a digit, i.e. a repeat.
+    fn repeat_ahead(&self) -> bool {
+        let mut ahead = self.chars.clone();
+
+        ahead.next() == Some('x') && ahead.next().is_some_and(|c| c.is_ascii_digit())
+    }
+
+    fn number(&mut self) -> Result<usize> {
+        let mut digits = String::new();
+
+        self.skip_whitespace();
+
+        while let Some(c) = self.chars.next_if(|c| c.is_ascii_digit()) {
+            digits.push(c);
+        }
+
+        if digits.is_empty() {
+            match self.peek() {
+                Some(c) => bail!("expected a number, found '{c}'"),
+                None => bail!("expected a number, found the end of the selection"),
+            }
+        }
+
+        digits.parse().context("could not parse number")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(txt: &str, len: usize) -> Result<Vec<usize>> {
+        Selection::parse(txt)?.resolve(&vec![None; len])
+    }
+
+    #[test]
+    fn lists_and_singles() -> Result<()> {
+        assert_eq!(resolve("3", 10)?, vec![3]);
+        assert_eq!(resolve("3, 1,3", 10)?, vec![3, 1, 3]);
+        assert_eq!(resolve("^1,^10", 10)?, vec![9, 0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn ranges() -> Result<()> {
+        assert_eq!(resolve("2-5", 10)?, vec![2, 3, 4]);
+        assert_eq!(resolve("7-", 10)?, vec![7, 8, 9]);
+        assert_eq!(resolve("-3", 10)?, vec![0, 1, 2]);
+        assert_eq!(resolve("-", 3)?, vec![0, 1, 2]);
+        assert_eq!(resolve("^3-^1", 10)?, vec![7, 8]);
+        assert_eq!(resolve("4-4", 10)?, Vec::<usize>::new());
+        assert_eq!(resolve("10-", 10)?, Vec::<usize>::new());
+
+        Ok(())
+    }
+
+    #[test]
+    fn steps() -> Result<()> {
+        assert_eq!(resolve("0-12:3", 12)?, vec![0, 3, 6, 9]);
+        assert_eq!(resolve("1-:4", 10)?, vec![1, 5, 9]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn repeats() -> Result<()> {
+        assert_eq!(resolve("5x2", 10)?, vec![5, 5]);
+        assert_eq!(resolve("(0-2)x2", 10)?, vec![0, 1, 0, 1]);
+        assert_eq!(resolve("1x2x2", 10)?, vec![1, 1, 1, 1]);
+        assert_eq!(resolve("3x0", 10)?, Vec::<usize>::new());
+
+        assert_eq!(resolve("0x10240", 10)?.len(), 10240);
+        assert!(resolve("0x10241", 10).is_err());
+        assert!(resolve("0x99999999999", 10).is_err());
+        assert!(resolve("0-x99999999999", 10).is_err());
+        assert!(resolve("(0x5000)x5000", 10).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn reversal_and_groups() -> Result<()> {
+        assert_eq!(resolve("rev(2-5)", 10)?, vec![4, 3, 2]);
+        assert_eq!(resolve("rev(1,(7-9))", 10)?, vec![8, 7, 1]);
+        assert_eq!(
+            resolve("0-3,3x4,rev(0-3)", 10)?,
+            vec![0, 1, 2, 3, 3, 3, 3, 2, 1, 0]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn names() -> Result<()> {
+        let names = [
+            Some("idle"),
+            None,
+            Some("run_01"),
+            Some("run_02"),
+            Some("walk-01"),
+            Some("box"),
+            Some("box2"),
+        ];
+        let resolve = |txt: &str| Selection::parse(txt)?.resolve(&names);
+
+        assert_eq!(resolve("@run_01")?, vec![2]);
+        assert_eq!(resolve("@idle-@run_02")?, vec![0, 1, 2]);
+        assert!(resolve("@walk").is_err());
+
+        assert_eq!(resolve("@run_02x2")?, vec![3, 3]);
+        assert_eq!(resolve("@run_01x2x2")?, vec![2, 2, 2, 2]);
+        assert_eq!(resolve("@boxx2")?, vec![5, 5]);
+        assert!(resolve("@box2").is_err());
+        assert_eq!(resolve("@\"box2\"x2")?, vec![6, 6]);
+        assert_eq!(resolve("@run_01-@boxx2")?, vec![2, 3, 4, 2, 3, 4]);
+
+        assert_eq!(resolve("@\"walk-01\"")?, vec![4]);
+        assert_eq!(resolve("@\"walk-01\"x2")?, vec![4, 4]);
+        assert_eq!(resolve("@\"run_01\"-@\"walk-01\"")?, vec![2, 3]);
+        assert!(resolve("@walk-01").is_err());
+        assert!(resolve("@\"walk-01").is_err());
+        assert!(resolve("@\"\"").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn mask() -> Result<()> {
+        let mask = Selection::parse("1,3-5,1")?.resolve_mask(&[None; 6])?;
+
+        assert_eq!(mask, vec![false, true, false, true, true, false]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn out_of_bounds() -> Result<()> {
+        assert!(resolve("10", 10).is_err());
+        assert!(resolve("^11", 10).is_err());
+        assert!(resolve("^0", 10).is_err());
+        assert!(resolve("3-11", 10).is_err());
+        assert!(resolve("12-", 10).is_err());
+        assert!(resolve("8-3", 10).is_err());
+        assert!(resolve("^2-^5", 10).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn syntax_errors() -> Result<()> {
+        for txt in [
+            "", ",", "3,", "a", "3-5:", "3-5:0", "rev(1", "rev 1", "(1,2", "1)", "5x", "@", "^",
+            "3 4",
+        ] {
+            assert!(Selection::parse(txt).is_err(), "`{txt}` should not parse");
+        }
+
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@ use clap::Args;
 
 #[derive(Debug, Args)]
 pub(crate) struct SlcOptions {
-    /// A single sprite to keep, or a range of sprites (e.g. `3-5`), upper-bound exclusive
+    /// The sprites to keep (e.g. `3-5`, upper-bound exclusive, or `0-3,7,^2-`), kept in their original order
     pub indices: String,
 
     /// Image file of the target sprite sheet
@@ -10,7 +10,7 @@ pub(crate) struct SlcOptions {
     pub image: String,
 
     /// Size of the target sprite sheet, e.g. `40x50` for pixel size, `3-3` for cell count,
-    /// `single` for a single image; `10` is the same as `10x10`
+    /// `single` for a single image; `10` is the same as `10x10`; can be omitted for sheets saved by sheety
     #[arg(short = 's', long = "size")]
-    pub size: String,
+    pub size: Option<String>,
 }
@@ -0,0 +1,22 @@
+use clap::Args;
+
+#[derive(Debug, Args)]
+pub(crate) struct ZipOptions {
+    /// How to lay the sprite sheets out: `interleave` to interleave their sprites (a0, b0, a1, b1...)
+    /// following the distribution, `lines` or `columns` to make each sprite sheet a line or a column
+    #[arg(short = 'l', long = "layout", default_value_t = String::from("interleave"))]
+    pub layout: String,
+
+    /// Each sprite sheet file path defined after a -i
+    #[arg(short = 'i', long = "image")]
+    pub images: Vec<String>,
+
+    /// The size of each sprite sheet, defined after a -s; their must be as many as the number of images, or none if using -S
+    #[arg(short = 's', long = "size")]
+    pub sizes: Vec<String>,
+
+    /// The default size, use it instead of -s if all sprite sheets have the same srpite size;
+    /// both can be omitted if all sprite sheets were saved by sheety
+    #[arg(short = 'S', long = "default_size", default_value_t = String::new())]
+    pub default_size: String,
+}
@@ -0,0 +1,20 @@
+use std::{path::PathBuf, process::Command};
+
+/// Gets a path in the temporary directory, unique to `name`.
+pub fn temp(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("sheety-cli-{}-{name}", std::process::id()))
+}
+
+/// Runs the sheety binary with `args`, and checks that it succeeds.
+pub fn sheety(args: &[&str]) {
+    let output = Command::new(env!("CARGO_BIN_EXE_sheety"))
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "sheety {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use common::{sheety, temp};
+use image::{Rgba, RgbaImage};
+use sheety::{Distribution, Sprite, SpriteSheet, UnorderedSpriteSheet};
+
+/// Gets the names of the frames of the project at `path`.
+fn names(path: &str) -> Vec<String> {
+    let (sheet, metadata) = SpriteSheet::load_project(path).unwrap();
+
+    metadata
+        .unordered(&sheet)
+        .unwrap()
+        .inner()
+        .iter()
+        .map(|sprite| sprite.name().unwrap().to_owned())
+        .collect()
+}
+
+#[test]
+fn select_by_name() {
+    let sprites: Vec<Sprite> = (0..4u8)
+        .map(|i| RgbaImage::from_pixel(2, 2, Rgba([i, 0, 0, 255])).into())
+        .collect();
+    let input = temp("unnamed.sheety");
+    SpriteSheet::from_unordered(
+        UnorderedSpriteSheet::new(sprites).unwrap(),
+        Distribution::FixedColumns(4),
+    )
+    .save(&input)
+    .unwrap();
+
+    let [input, named, sequence, sliced] = [
+        input,
+        temp("named.sheety"),
+        temp("seq.sheety"),
+        temp("slc.sheety"),
+    ]
+    .map(|path| path.to_str().unwrap().to_owned());
+
+    sheety(&["name", "walk-##", "-i", &input, "-o", &named]);
+    assert_eq!(names(&named), ["walk-00", "walk-01", "walk-02", "walk-03"]);
+
+    sheety(&[
+        "seq",
+        "@\"walk-02\"x2,@\"walk-00\"",
+        "-i",
+        &named,
+        "-o",
+        &sequence,
+    ]);
+    assert_eq!(names(&sequence), ["walk-02", "walk-02", "walk-00"]);
+
+    sheety(&[
+        "slc",
+        "@\"walk-01\"-@\"walk-03\"",
+        "-i",
+        &named,
+        "-o",
+        &sliced,
+    ]);
+    assert_eq!(names(&sliced), ["walk-01", "walk-02"]);
+}
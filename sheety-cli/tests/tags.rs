@@ -0,0 +1,152 @@
+mod common;
+
+use std::{ops::Range, path::PathBuf};
+
+use common::{sheety, temp};
+use image::{Rgba, RgbaImage};
+use sheety::{AnimationTag, SpriteCell, SpriteSheet};
+
+/// Saves a 3x2 sheet of 4x4 cells, with five frames and the last cell left empty, tagged `idle` and `walk`.
+fn save_tagged(path: &PathBuf) {
+    let mut sheet = SpriteSheet::new((3, 2), (4, 4));
+
+    for i in 0..5 {
+        sheet
+            .set_cell(
+                (i % 3, i / 3),
+                SpriteCell::Sprite(RgbaImage::from_pixel(4, 4, Rgba([i as u8, 0, 0, 255])).into()),
+            )
+            .unwrap();
+    }
+
+    let tags = [tag("idle", 0..2), tag("walk", 2..5)];
+    sheet.save_with_tags(path, &tags).unwrap();
+}
+
+fn tag(name: &str, frames: Range<usize>) -> AnimationTag {
+    AnimationTag {
+        name: name.to_owned(),
+        frames,
+    }
+}
+
+fn tags(path: &PathBuf) -> Vec<AnimationTag> {
+    let extension = path.extension().unwrap_or_default();
+
+    let (_, metadata) = match extension.to_str() {
+        Some("sheety") => SpriteSheet::load_project(path),
+        _ => SpriteSheet::load_with_metadata(path),
+    }
+    .unwrap();
+
+    metadata.tags
+}
+
+#[test]
+fn tags_survive_in_place_steps() {
+    let (input, markers) = (temp("input.sheety"), temp("markers.png"));
+    save_tagged(&input);
+    RgbaImage::new(12, 8).save(&markers).unwrap();
+
+    let steps: [&[&str]; 4] = [
+        &["pivot", "-a", "bottom"],
+        &["boxes", "-m", markers.to_str().unwrap()],
+        &["outline"],
+        &["nineslice", "-b", "1"],
+    ];
+    let expected = vec![tag("idle", 0..2), tag("walk", 2..5)];
+    let mut previous = input;
+
+    for (i, step) in steps.into_iter().enumerate() {
+        let extension = if i % 2 == 0 { "sheety" } else { "png" };
+        let output = temp(&format!("step{i}.{extension}"));
+
+        let mut args = step.to_vec();
+        args.extend([
+            "-i",
+            previous.to_str().unwrap(),
+            "-o",
+            output.to_str().unwrap(),
+        ]);
+        sheety(&args);
+
+        assert_eq!(tags(&output), expected, "after {}", step[0]);
+        previous = output;
+    }
+}
+
+#[test]
+fn tags_follow_frames() {
+    let input = temp("frames.sheety");
+    save_tagged(&input);
+
+    let (deleted, reversed) = (temp("deleted.sheety"), temp("reversed.sheety"));
+    let path = |path: &PathBuf| path.to_str().unwrap().to_owned();
+
+    sheety(&["del", "1-3", "-i", &path(&input), "-o", &path(&deleted)]);
+    assert_eq!(tags(&deleted), vec![tag("idle", 0..1), tag("walk", 1..3)]);
+
+    sheety(&["rev", "-i", &path(&deleted), "-o", &path(&reversed)]);
+    assert_eq!(tags(&reversed), vec![tag("idle", 2..3), tag("walk", 0..2)]);
+}
+
+#[test]
+fn tags_follow_retimed_zipped_and_mirrored_frames() {
+    let input = temp("more.sheety");
+    save_tagged(&input);
+
+    let (retimed, zipped, mirrored) = (
+        temp("retimed.sheety"),
+        temp("zipped.sheety"),
+        temp("mirrored.sheety"),
+    );
+    let path = |path: &PathBuf| path.to_str().unwrap().to_owned();
+
+    sheety(&[
+        "retime",
+        "-f",
+        "12",
+        "-t",
+        "24",
+        "-i",
+        &path(&input),
+        "-o",
+        &path(&retimed),
+    ]);
+    assert_eq!(tags(&retimed), vec![tag("idle", 0..4), tag("walk", 4..10)]);
+
+    sheety(&[
+        "zip",
+        "-l",
+        "lines",
+        "-i",
+        &path(&input),
+        "-i",
+        &path(&input),
+        "-o",
+        &path(&zipped),
+    ]);
+    assert_eq!(
+        tags(&zipped),
+        vec![
+            tag("idle", 0..2),
+            tag("walk", 2..5),
+            tag("idle", 5..7),
+            tag("walk", 7..10)
+        ]
+    );
+
+    // The west line is the east one flipped, so the tags stay on the east line.
+    sheety(&[
+        "mirror-dirs",
+        "-f",
+        "E,S",
+        "-t",
+        "E,S,W",
+        "-i",
+        &path(&input),
+        "-o",
+        &path(&mirrored),
+    ]);
+    assert_eq!(tags(&mirrored), vec![tag("idle", 0..2), tag("walk", 2..5)]);
+}
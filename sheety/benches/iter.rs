@@ -0,0 +1,59 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use image::RgbaImage;
+use sheety::*;
+
+/// Makes a square sheet of `side * side` cells, every other cell holding a 1x1 sprite.
+fn make_sheet(side: usize) -> SpriteSheet {
+    let mut sheet = SpriteSheet::new((side, side), (1, 1));
+
+    for (i, cell) in sheet.cells_mut().enumerate() {
+        if i % 2 == 0 {
+            *cell = SpriteCell::Sprite(Sprite::from(RgbaImage::new(1, 1)));
+        }
+    }
+
+    sheet
+}
+
+fn iter(c: &mut Criterion) {
+    let mut group = c.benchmark_group("iter");
+
+    // 2 500, 10 000 and 40 000 cells: times should grow linearly with the amount of cells
+    for side in [50, 100, 200] {
+        let mut sheet = make_sheet(side);
+        group.throughput(Throughput::Elements((side * side) as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("cells", side * side),
+            &sheet,
+            |b, sheet| b.iter(|| black_box(sheet.cells().filter(|cell| cell.is_sprite()).count())),
+        );
+
+        group.bench_function(BenchmarkId::new("cells_mut", side * side), |b| {
+            b.iter(|| {
+                for cell in sheet.cells_mut() {
+                    black_box(cell);
+                }
+            })
+        });
+
+        group.bench_with_input(
+            BenchmarkId::new("columns", side * side),
+            &sheet,
+            |b, sheet| b.iter(|| black_box(sheet.columns().flatten().count())),
+        );
+
+        group.bench_function(BenchmarkId::new("into_iter", side * side), |b| {
+            b.iter_batched(
+                || make_sheet(side),
+                |sheet| black_box(sheet.into_iter().filter(|cell| cell.is_sprite()).count()),
+                criterion::BatchSize::LargeInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, iter);
+criterion_main!(benches);
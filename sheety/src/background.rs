@@ -0,0 +1,194 @@
+use image::{GenericImageView, Rgba};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{sprite_cell::SpriteCell, Sprite, SpriteSheet};
+
+/// Which pixels of a sheet are background, to tell empty cells from frames, e.g. in sheets with an opaque
+/// background. Fully transparent pixels are always background. See [SpriteSheet::clear_background] and
+/// [SpriteSheet::key_out].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Background {
+    /// Pixels whose alpha is lower than or equal to the threshold, e.g. to ignore faint stray pixels. `0` only
+    /// makes fully transparent pixels background, like [Sprite::is_empty].
+    Alpha(u8),
+    /// Pixels of a colour key, e.g. the magenta background of an opaque sheet.
+    Key {
+        /// The colour of the background.
+        colour: Rgba<u8>,
+        /// How much each channel of a pixel can differ from `colour`, e.g. because of JPEG artifacts.
+        tolerance: u8,
+    },
+    /// Same as [Background::Key], the colour being the most common among the four corners of the image.
+    Auto {
+        /// How much each channel of a pixel can differ from the colour of the background.
+        tolerance: u8,
+    },
+}
+
+impl Default for Background {
+    fn default() -> Self {
+        Self::Alpha(0)
+    }
+}
+
+impl Background {
+    /// Turns [Background::Auto] into a [Background::Key] whose colour is the most common among `corners`, the
+    /// top left one winning ties. Other backgrounds are returned as is.
+    pub fn resolve(self, corners: [Rgba<u8>; 4]) -> Self {
+        match self {
+            Self::Auto { tolerance } => {
+                let count = |colour: &Rgba<u8>| {
+                    corners
+                        .iter()
+                        .filter(|corner| close(&corner.0, &colour.0, tolerance))
+                        .count()
+                };
+
+                // `max_by_key` returns the last of equal colours, so corners are reversed for the first to win.
+                let colour = corners.iter().rev().max_by_key(|corner| count(corner));
+
+                Self::Key {
+                    colour: *colour.expect("there are always four corners"),
+                    tolerance,
+                }
+            }
+            background => background,
+        }
+    }
+
+    /// Returns `true` if the RGBA `pixel` is background. [Background::Auto] should be resolved first, otherwise
+    /// only fully transparent pixels are background.
+    fn contains(&self, pixel: &[u8]) -> bool {
+        pixel[3] == 0
+            || match *self {
+                Self::Alpha(threshold) => pixel[3] <= threshold,
+                Self::Key { colour, tolerance } => close(pixel, &colour.0, tolerance),
+                Self::Auto { .. } => false,
+            }
+    }
+}
+
+impl Sprite {
+    /// Returns `true` if every pixel of this [Sprite] is `background`. [Background::Auto] uses the corners of
+    /// this [Sprite]. With the `rayon` feature, lines of pixels are checked in parallel.
+    pub fn is_empty_with(&self, background: Background) -> bool {
+        let background = background.resolve(self.corners());
+
+        self.par_raw_rows()
+            .all(|row| row.chunks_exact(4).all(|px| background.contains(px)))
+    }
+
+    /// Makes the `background` pixels of this [Sprite] fully transparent, e.g. to turn the colour key of an opaque
+    /// sheet into alpha. [Background::Auto] uses the corners of this [Sprite]. Pixels are only copied if some
+    /// of them are background.
+    pub fn key_out(&mut self, background: Background) {
+        let background = background.resolve(self.corners());
+        key_out(self, background);
+    }
+
+    /// Gets the top left, top right, bottom left and bottom right pixels of this [Sprite].
+    fn corners(&self) -> [Rgba<u8>; 4] {
+        let view = self.view();
+        let (width, height) = view.dimensions();
+
+        if width == 0 || height == 0 {
+            return [Rgba([0, 0, 0, 0]); 4];
+        }
+
+        [
+            (0, 0),
+            (width - 1, 0),
+            (0, height - 1),
+            (width - 1, height - 1),
+        ]
+        .map(|(x, y)| view.get_pixel(x, y))
+    }
+}
+
+impl SpriteSheet {
+    /// Empties the cells of this [SpriteSheet] whose pixels are all `background`, so that
+    /// [SpriteSheet::into_unordered] leaves them out, e.g. for sheets with an opaque background.
+    /// [Background::Auto] uses the corners of the whole sheet. Pixels are left as they are, see
+    /// [SpriteSheet::key_out] to make the background transparent as well.
+    pub fn clear_background(&mut self, background: Background) {
+        let background = background.resolve(self.corners());
+
+        for cell in self.cells_mut() {
+            if let SpriteCell::Sprite(sprite) = cell {
+                if sprite.is_empty_with(background) {
+                    *cell = SpriteCell::Empty;
+                }
+            }
+        }
+    }
+
+    /// Makes the `background` pixels of the [Sprite]s of this [SpriteSheet] fully transparent, e.g. to turn the
+    /// colour key of an opaque sheet into alpha, and empties the cells left without visible pixels.
+    /// [Background::Auto] uses the corners of the whole sheet.
+    pub fn key_out(&mut self, background: Background) {
+        let background = background.resolve(self.corners());
+
+        for cell in self.cells_mut() {
+            if let SpriteCell::Sprite(sprite) = cell {
+                key_out(sprite, background);
+
+                if sprite.is_empty() {
+                    *cell = SpriteCell::Empty;
+                }
+            }
+        }
+    }
+
+    /// Gets the top left, top right, bottom left and bottom right pixels of the image of this [SpriteSheet],
+    /// empty cells being fully transparent.
+    fn corners(&self) -> [Rgba<u8>; 4] {
+        let size = self.size();
+
+        if size.0 == 0 || size.1 == 0 {
+            return [Rgba([0, 0, 0, 0]); 4];
+        }
+
+        let corner = |coords, corner: usize| match self.get_cell(coords) {
+            Ok(SpriteCell::Sprite(sprite)) => sprite.corners()[corner],
+            _ => Rgba([0, 0, 0, 0]),
+        };
+
+        [
+            corner((0, 0), 0),
+            corner((size.0 - 1, 0), 1),
+            corner((0, size.1 - 1), 2),
+            corner((size.0 - 1, size.1 - 1), 3),
+        ]
+    }
+}
+
+/// Same as [Sprite::key_out], but [Background::Auto] must already be resolved.
+fn key_out(sprite: &mut Sprite, background: Background) {
+    let keyed = sprite.par_raw_rows().any(|row| {
+        row.chunks_exact(4)
+            .any(|px| px[3] != 0 && background.contains(px))
+    });
+
+    if !keyed {
+        return;
+    }
+
+    let image = sprite.image_mut();
+
+    #[cfg(feature = "rayon")]
+    let pixels = image.par_chunks_exact_mut(4);
+    #[cfg(not(feature = "rayon"))]
+    let pixels = image.chunks_exact_mut(4);
+
+    pixels.for_each(|px| {
+        if background.contains(px) {
+            px.copy_from_slice(&[0, 0, 0, 0]);
+        }
+    });
+}
+
+/// Returns `true` if each channel of `a` differs from the one of `b` by `tolerance` at most.
+fn close(a: &[u8], b: &[u8], tolerance: u8) -> bool {
+    a.iter().zip(b).all(|(a, b)| a.abs_diff(*b) <= tolerance)
+}
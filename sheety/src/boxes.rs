@@ -0,0 +1,79 @@
+use std::{fmt, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, Result},
+    utils::IVec2,
+};
+
+/// What a [CollisionBox] is used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BoxKind {
+    /// Where the character can be hit.
+    Hurtbox,
+    /// Where the character hits others.
+    Hitbox,
+}
+
+/// A rectangle of a [Sprite][crate::Sprite], in pixels from its top left corner, e.g. the hurtbox of a fighter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CollisionBox {
+    /// What the box is used for.
+    pub kind: BoxKind,
+    /// The position of the top left corner of the box.
+    pub position: IVec2,
+    /// The size of the box.
+    pub size: IVec2,
+}
+
+impl CollisionBox {
+    /// Moves this [CollisionBox] with `map`, which maps points of a [Sprite][crate::Sprite] to points of a
+    /// [Sprite][crate::Sprite] of size `size`, and clips it to that size. Returns [None] if nothing is left.
+    pub(crate) fn map<F>(&self, size: IVec2, map: F) -> Option<Self>
+    where
+        F: Fn((f32, f32)) -> (f32, f32),
+    {
+        let a = map((self.position.0 as f32, self.position.1 as f32));
+        let b = map((
+            (self.position.0 + self.size.0) as f32,
+            (self.position.1 + self.size.1) as f32,
+        ));
+
+        let clip = |v: f32, max: usize| f32::clamp(f32::round(v), 0.0, max as f32) as usize;
+        let (left, right) = (clip(a.0.min(b.0), size.0), clip(a.0.max(b.0), size.0));
+        let (top, bottom) = (clip(a.1.min(b.1), size.1), clip(a.1.max(b.1), size.1));
+
+        (left < right && top < bottom).then_some(Self {
+            kind: self.kind,
+            position: (left, top),
+            size: (right - left, bottom - top),
+        })
+    }
+}
+
+impl fmt::Display for BoxKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Hurtbox => "hurtbox",
+            Self::Hitbox => "hitbox",
+        })
+    }
+}
+
+impl FromStr for BoxKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "hurtbox" => Ok(Self::Hurtbox),
+            "hitbox" => Ok(Self::Hitbox),
+            unknown => Err(Error::InvalidMetadata(format!(
+                "unknown box kind `{unknown}` (expected hurtbox or hitbox)"
+            ))),
+        }
+    }
+}
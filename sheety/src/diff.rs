@@ -0,0 +1,249 @@
+use std::fmt;
+
+use image::{Rgba, RgbaImage};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{
+    error::{Error, Result},
+    sprite_cell::SpriteCell,
+    utils::IVec2,
+    Sprite, SpriteSheet,
+};
+
+/// How a cell differs between two versions of a [SpriteSheet], see [SpriteSheet::diff].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CellChange {
+    /// The cell was empty, and now holds a frame.
+    Added,
+    /// The cell held a frame, and is now empty.
+    Removed,
+    /// The pixels of the frame of the cell changed.
+    Changed {
+        /// The amount of pixels that changed.
+        pixels: usize,
+        /// The position of the top left corner of the smallest rectangle holding every changed pixel, in pixels
+        /// from the top left corner of the cell.
+        position: IVec2,
+        /// The size of the smallest rectangle holding every changed pixel.
+        size: IVec2,
+    },
+}
+
+/// A cell that differs between two versions of a [SpriteSheet].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellDiff {
+    /// The coordinates of the cell.
+    pub coords: IVec2,
+    /// The name of the frame of the cell in the new version, or in the old one if it was removed, if it has one.
+    pub name: Option<String>,
+    /// How the cell differs.
+    pub change: CellChange,
+}
+
+/// The differences between two versions of a [SpriteSheet], see [SpriteSheet::diff]. Its [Display][fmt::Display]
+/// implementation writes a summary with one line per cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SheetDiff {
+    /// The size of the old version, in cells.
+    pub old_size: IVec2,
+    /// The size of the new version, in cells.
+    pub new_size: IVec2,
+    /// The cells that differ, line after line.
+    pub cells: Vec<CellDiff>,
+}
+
+impl SheetDiff {
+    /// Returns `true` if both versions have the same size and the same pixels.
+    pub fn is_empty(&self) -> bool {
+        self.old_size == self.new_size && self.cells.is_empty()
+    }
+}
+
+impl SpriteSheet {
+    /// Compares this [SpriteSheet], the old version, with `new`, cell by cell. If the sheets don't have the same
+    /// size, missing cells are empty. Fully transparent pixels are equal whatever their colour.
+    /// With the `rayon` feature, cells are compared in parallel.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::MismatchedSpriteSize] if the sheets don't have the same cell size.
+    pub fn diff(&self, new: &SpriteSheet) -> Result<SheetDiff> {
+        check_cell_size(self, new)?;
+
+        let size = union_size(self, new);
+
+        #[cfg(feature = "rayon")]
+        let cells = (0..size.0 * size.1).into_par_iter();
+        #[cfg(not(feature = "rayon"))]
+        let cells = 0..size.0 * size.1;
+
+        let cells = cells
+            .filter_map(|i| {
+                let coords = (i % size.0, i / size.0);
+
+                let change = match (sprite_at(self, coords), sprite_at(new, coords)) {
+                    (None, None) => return None,
+                    (None, Some(_)) => CellChange::Added,
+                    (Some(_), None) => CellChange::Removed,
+                    (Some(old), Some(new)) => changed_pixels(old, new)?,
+                };
+
+                let name = sprite_at(new, coords)
+                    .or(sprite_at(self, coords))
+                    .and_then(Sprite::name)
+                    .map(str::to_owned);
+
+                Some(CellDiff {
+                    coords,
+                    name,
+                    change,
+                })
+            })
+            .collect();
+
+        Ok(SheetDiff {
+            old_size: self.size(),
+            new_size: new.size(),
+            cells,
+        })
+    }
+
+    /// Draws the pixels of `new` that differ from this [SpriteSheet] in `colour`, over a faded copy of `new`, e.g. to
+    /// review changes. Removed pixels are drawn as well. The image is as big as the biggest sheet.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::MismatchedSpriteSize] if the sheets don't have the same cell size.
+    pub fn diff_image(&self, new: &SpriteSheet, colour: Rgba<u8>) -> Result<RgbaImage> {
+        check_cell_size(self, new)?;
+
+        let (size, cell_size) = (union_size(self, new), self.cell_size());
+        let mut image =
+            RgbaImage::new((size.0 * cell_size.0) as u32, (size.1 * cell_size.1) as u32);
+
+        let empty = vec![0; cell_size.0 * 4];
+
+        for i in 0..size.0 * size.1 {
+            let coords = (i % size.0, i / size.0);
+            let (old, new) = (rows(self, coords, &empty), rows(new, coords, &empty));
+
+            for (y, (old, new)) in old.into_iter().zip(new).enumerate() {
+                for (x, (old, new)) in old.chunks_exact(4).zip(new.chunks_exact(4)).enumerate() {
+                    let pixel = if same(old, new) {
+                        Rgba([new[0], new[1], new[2], new[3] / 4])
+                    } else {
+                        colour
+                    };
+
+                    image.put_pixel(
+                        (coords.0 * cell_size.0 + x) as u32,
+                        (coords.1 * cell_size.1 + y) as u32,
+                        pixel,
+                    );
+                }
+            }
+        }
+
+        Ok(image)
+    }
+}
+
+impl fmt::Display for SheetDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.old_size != self.new_size {
+            writeln!(
+                f,
+                "size changed from {}x{} to {}x{} cells",
+                self.old_size.0, self.old_size.1, self.new_size.0, self.new_size.1
+            )?;
+        }
+
+        for cell in self.cells.iter() {
+            write!(f, "cell {}x{}", cell.coords.0, cell.coords.1)?;
+
+            if let Some(name) = &cell.name {
+                write!(f, " ({name})")?;
+            }
+
+            match cell.change {
+                CellChange::Added => writeln!(f, ": added")?,
+                CellChange::Removed => writeln!(f, ": removed")?,
+                CellChange::Changed {
+                    pixels,
+                    position,
+                    size,
+                } => writeln!(
+                    f,
+                    ": {pixels} pixels changed in {}x{} at {}x{}",
+                    size.0, size.1, position.0, position.1
+                )?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn check_cell_size(old: &SpriteSheet, new: &SpriteSheet) -> Result<()> {
+    if old.cell_size() != new.cell_size() {
+        return Err(Error::MismatchedSpriteSize {
+            required: old.cell_size(),
+            provided: new.cell_size(),
+        });
+    }
+
+    Ok(())
+}
+
+fn union_size(old: &SpriteSheet, new: &SpriteSheet) -> IVec2 {
+    (
+        usize::max(old.size().0, new.size().0),
+        usize::max(old.size().1, new.size().1),
+    )
+}
+
+/// Gets the [Sprite] of the cell of `sheet` at `coords`, or [None] if it is empty or out of bounds.
+fn sprite_at(sheet: &SpriteSheet, coords: IVec2) -> Option<&Sprite> {
+    match sheet.get_cell(coords) {
+        Ok(SpriteCell::Sprite(sprite)) => Some(sprite),
+        _ => None,
+    }
+}
+
+/// Gets the raw RGBA bytes of each line of pixels of the cell of `sheet` at `coords`, or `empty` for each line if
+/// the cell is empty or out of bounds.
+fn rows<'a>(sheet: &'a SpriteSheet, coords: IVec2, empty: &'a [u8]) -> Vec<&'a [u8]> {
+    match sprite_at(sheet, coords) {
+        Some(sprite) => sprite.raw_rows().collect(),
+        None => vec![empty; sheet.cell_size().1],
+    }
+}
+
+/// Returns `true` if the RGBA pixels `a` and `b` are equal, or both fully transparent.
+fn same(a: &[u8], b: &[u8]) -> bool {
+    a == b || (a[3] == 0 && b[3] == 0)
+}
+
+/// Counts the pixels that differ between `old` and `new`, and gets the rectangle holding them, or [None] if they
+/// don't differ.
+fn changed_pixels(old: &Sprite, new: &Sprite) -> Option<CellChange> {
+    let mut pixels = 0;
+    let (mut min, mut max) = ((usize::MAX, usize::MAX), (0, 0));
+
+    for (y, (old, new)) in old.raw_rows().zip(new.raw_rows()).enumerate() {
+        for (x, (old, new)) in old.chunks_exact(4).zip(new.chunks_exact(4)).enumerate() {
+            if !same(old, new) {
+                pixels += 1;
+                min = (min.0.min(x), min.1.min(y));
+                max = (max.0.max(x + 1), max.1.max(y + 1));
+            }
+        }
+    }
+
+    (pixels > 0).then(|| CellChange::Changed {
+        pixels,
+        position: min,
+        size: (max.0 - min.0, max.1 - min.1),
+    })
+}
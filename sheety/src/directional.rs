@@ -0,0 +1,216 @@
+use std::{fmt, str::FromStr};
+
+use crate::{
+    error::{Error, Result},
+    sprite_cell::SpriteCell,
+    SpriteSheet,
+};
+
+/// One of the 8 directions a top-down character can face.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Facing right.
+    East,
+    /// Facing up and right.
+    NorthEast,
+    /// Facing up.
+    North,
+    /// Facing up and left.
+    NorthWest,
+    /// Facing left.
+    West,
+    /// Facing down and left.
+    SouthWest,
+    /// Facing down.
+    South,
+    /// Facing down and right.
+    SouthEast,
+}
+
+impl Direction {
+    /// All 8 directions, counter-clockwise, starting from [Direction::East].
+    pub const ALL: [Direction; 8] = [
+        Self::East,
+        Self::NorthEast,
+        Self::North,
+        Self::NorthWest,
+        Self::West,
+        Self::SouthWest,
+        Self::South,
+        Self::SouthEast,
+    ];
+
+    /// Gets the short name of this [Direction], e.g. `"NE"` for [Direction::NorthEast].
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::East => "E",
+            Self::NorthEast => "NE",
+            Self::North => "N",
+            Self::NorthWest => "NW",
+            Self::West => "W",
+            Self::SouthWest => "SW",
+            Self::South => "S",
+            Self::SouthEast => "SE",
+        }
+    }
+
+    /// Gets the [Direction] this one becomes when flipped horizontally, e.g. [Direction::West] for
+    /// [Direction::East]. [Direction::North] and [Direction::South] stay the same.
+    pub fn mirrored(&self) -> Self {
+        match self {
+            Self::East => Self::West,
+            Self::NorthEast => Self::NorthWest,
+            Self::North => Self::North,
+            Self::NorthWest => Self::NorthEast,
+            Self::West => Self::East,
+            Self::SouthWest => Self::SouthEast,
+            Self::South => Self::South,
+            Self::SouthEast => Self::SouthWest,
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Direction {
+    type Err = Error;
+
+    /// Parses the short name of a [Direction], e.g. `"NE"`, case insensitively.
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|direction| direction.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| Error::UnknownDirection(s.to_owned()))
+    }
+}
+
+/// A [SpriteSheet] where each line holds the frames of one [Direction], so that cells can be
+/// addressed by `(direction, frame)`.
+pub struct DirectionalSheet {
+    sheet: SpriteSheet,
+    directions: Vec<Direction>,
+}
+
+impl DirectionalSheet {
+    /// Makes a [DirectionalSheet] out of a [SpriteSheet], where line `i` holds the frames of `directions[i]`.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::MismatchedLength] if there isn't exactly one [Direction] per line.
+    /// - Will return [Error::DuplicateDirection] if a [Direction] is given more than once.
+    pub fn new(sheet: SpriteSheet, directions: Vec<Direction>) -> Result<Self> {
+        if directions.len() != sheet.size().1 {
+            return Err(Error::MismatchedLength {
+                required: sheet.size().1,
+                provided: directions.len(),
+            });
+        }
+
+        for (i, direction) in directions.iter().enumerate() {
+            if directions[..i].contains(direction) {
+                return Err(Error::DuplicateDirection(*direction));
+            }
+        }
+
+        Ok(Self { sheet, directions })
+    }
+
+    /// Gets the [Direction]s of this [DirectionalSheet], in the order of the lines of the sheet.
+    #[inline(always)]
+    pub fn directions(&self) -> &[Direction] {
+        &self.directions
+    }
+
+    /// Gets the amount of frames per [Direction].
+    #[inline(always)]
+    pub fn frame_count(&self) -> usize {
+        self.sheet.size().0
+    }
+
+    /// Returns `true` if this [DirectionalSheet] has frames for `direction`.
+    pub fn has_direction(&self, direction: Direction) -> bool {
+        self.directions.contains(&direction)
+    }
+
+    fn line(&self, direction: Direction) -> Result<usize> {
+        self.directions
+            .iter()
+            .position(|d| *d == direction)
+            .ok_or(Error::MissingDirection(direction))
+    }
+
+    /// Returns an immutable reference to the cell of `frame` facing `direction`.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::MissingDirection] if there are no frames for `direction`.
+    /// - Will return [Error::OutOfBounds] if `frame` is out of bounds.
+    pub fn get_cell(&self, direction: Direction, frame: usize) -> Result<&SpriteCell> {
+        let line = self.line(direction)?;
+        self.sheet.get_cell((frame, line))
+    }
+
+    /// Returns a mutable reference to the cell of `frame` facing `direction`.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::MissingDirection] if there are no frames for `direction`.
+    /// - Will return [Error::OutOfBounds] if `frame` is out of bounds.
+    pub fn get_cell_mut(&mut self, direction: Direction, frame: usize) -> Result<&mut SpriteCell> {
+        let line = self.line(direction)?;
+        self.sheet.get_cell_mut((frame, line))
+    }
+
+    /// Makes a new [DirectionalSheet] with a line for each of `directions`, in that order. Directions that are
+    /// missing from this [DirectionalSheet] are synthesised by flipping the frames of their
+    /// [mirrored][Direction::mirrored] direction horizontally, e.g. [Direction::West] from [Direction::East].
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::MissingDirection] if neither a direction nor its mirrored direction have frames.
+    /// - Will return [Error::DuplicateDirection] if a [Direction] is given more than once.
+    pub fn mirror_missing(&self, directions: &[Direction]) -> Result<Self> {
+        let mut sheet = SpriteSheet::new(
+            (self.frame_count(), directions.len()),
+            self.sheet.cell_size(),
+        );
+
+        for (y, direction) in directions.iter().enumerate() {
+            let (source, flip) = if self.has_direction(*direction) {
+                (*direction, false)
+            } else if self.has_direction(direction.mirrored()) {
+                (direction.mirrored(), true)
+            } else {
+                return Err(Error::MissingDirection(*direction));
+            };
+
+            for x in 0..self.frame_count() {
+                let cell = match self.get_cell(source, x)? {
+                    SpriteCell::Sprite(sprite) if flip => {
+                        SpriteCell::Sprite(sprite.flip_horizontal())
+                    }
+                    cell => cell.clone(),
+                };
+
+                sheet.set_cell((x, y), cell)?;
+            }
+        }
+
+        Self::new(sheet, directions.to_vec())
+    }
+
+    /// Gets an immutable reference to the underlying [SpriteSheet].
+    #[inline(always)]
+    pub fn sheet(&self) -> &SpriteSheet {
+        &self.sheet
+    }
+
+    /// Consumes this [DirectionalSheet] and returns its underlying [SpriteSheet].
+    pub fn into_sheet(self) -> SpriteSheet {
+        self.sheet
+    }
+}
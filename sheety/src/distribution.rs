@@ -1,8 +1,12 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::utils::IVec2;
 
 /// Reprensents a constraint to follow when building [SpriteSheet][crate::SpriteSheet] from an
 /// [UnorderedSpriteSheet][crate::UnorderedSpriteSheet].
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Distribution {
     /// Requires that the [SpriteSheet][crate::SpriteSheet] has a fixed amount of columns.
     /// The number of lines will then depend on the amount of [Sprite][crate::Sprite]s in the sheet.
@@ -4,7 +4,7 @@ use image::ImageError;
 
 use thiserror::Error;
 
-use crate::utils::IVec2;
+use crate::{utils::IVec2, Direction, NineSlice};
 
 /// An error returned by a [sheety][crate] function.
 #[derive(Debug, Error)]
@@ -27,6 +27,22 @@ pub enum Error {
         /// The first size found to be different from the required size.
         provided: IVec2,
     },
+    /// Attempted to pair a [SpriteSheet][crate::SpriteSheet] with a companion sheet, e.g. a marker layer, that
+    /// doesn't have the same size or cell size.
+    #[error(
+        "the companion sheet is `{provided:?}` cells of `{provided_cell_size:?}` pixels, \
+	not `{required:?}` cells of `{required_cell_size:?}` pixels"
+    )]
+    MismatchedSheetSize {
+        /// The size of the sheet, in cells.
+        required: IVec2,
+        /// The size of the cells of the sheet, in pixels.
+        required_cell_size: IVec2,
+        /// The size of the companion sheet, in cells.
+        provided: IVec2,
+        /// The size of the cells of the companion sheet, in pixels.
+        provided_cell_size: IVec2,
+    },
     /// Attempted to construct an [UnorderedSpriteSheet][crate::UnorderedSpriteSheet] with no [Sprite][crate::Sprite]s.
     /// At least one [Sprite][crate::Sprite] is needed to guess the cell size of the sheet.
     #[error("attempted to make an empty unordered sprite sheet")]
@@ -40,9 +56,74 @@ pub enum Error {
         /// The amount of [Sprite][crate::Sprite]s that were able to be fitted.
         amount_fitted: u32,
     },
+    /// Attempted to give per-[Sprite][crate::Sprite] values for a different amount of [Sprite][crate::Sprite]s
+    /// than there are in the sheet.
+    #[error("expected one value per sprite (`{required}` sprites, `{provided}` values provided)")]
+    MismatchedLength {
+        /// The amount of [Sprite][crate::Sprite]s in the sheet.
+        required: usize,
+        /// The amount of values that were provided.
+        provided: usize,
+    },
+    /// Attempted to use a frame rate that is not finite and strictly positive, or too far from another one.
+    #[error("the frame rate `{0}` should be finite, strictly positive, and not too far from the other frame rate")]
+    InvalidFrameRate(f32),
+    /// Attempted to parse an unknown [Direction] name.
+    #[error("unknown direction `{0}` (expected one of E, NE, N, NW, W, SW, S, SE)")]
+    UnknownDirection(String),
+    /// A [Direction] was given more than once to a [DirectionalSheet][crate::DirectionalSheet].
+    #[error("direction `{0}` was given more than once")]
+    DuplicateDirection(Direction),
+    /// Attempted to get the frames of a [Direction] that a [DirectionalSheet][crate::DirectionalSheet] doesn't have.
+    #[error("there are no frames for direction `{0}`")]
+    MissingDirection(Direction),
+    /// Attempted to read a sheet on its own from an image without embedded
+    /// [SheetMetadata][crate::SheetMetadata].
+    #[error("the image has no embedded sheet metadata")]
+    MissingMetadata,
+    /// The [SheetMetadata][crate::SheetMetadata] of a sheet could not be parsed, or doesn't match the sheet.
+    #[error("invalid sheet metadata: {0}")]
+    InvalidMetadata(String),
+    /// Attempted to read a `.sheety` project from invalid data.
+    #[error("invalid sheety project: {0}")]
+    InvalidProject(String),
+    /// Attempted to resize a [Sprite][crate::Sprite] as a nine-slice without [NineSlice] borders.
+    #[error("the sprite has no nine-slice borders")]
+    MissingNineSlice,
+    /// Attempted to resize a [Sprite][crate::Sprite] as a nine-slice, from or to a size its [NineSlice] borders
+    /// don't fit in.
+    #[error("nine-slice borders `{borders}` don't fit in `{size:?}` pixels")]
+    InvalidNineSlice {
+        /// The borders of the [Sprite][crate::Sprite].
+        borders: NineSlice,
+        /// The size the borders don't fit in.
+        size: IVec2,
+    },
+    /// Attempted to slice an image into cells of 0 pixels, or into 0 cells.
+    #[error("cells should be at least 1x1 pixels, and sheets at least 1x1 cells")]
+    ZeroCellSize,
+    /// Attempted to strictly slice an image that isn't made of whole cells, which would leave pixels out.
+    #[error(
+        "the image of `{image_size:?}` pixels isn't made of whole cells of `{cell_size:?}` pixels"
+    )]
+    IndivisibleSheet {
+        /// The size of the image, in pixels.
+        image_size: IVec2,
+        /// The size of the cells, in pixels.
+        cell_size: IVec2,
+    },
     /// Encapsulates an [image::ImageError] from the [image] crate.
     #[error("internal image error")]
     ImageError(#[from] ImageError),
+    /// Encapsulates a [png::EncodingError] from the [png] crate, returned when streaming a sheet to a PNG image.
+    #[error("internal png encoding error")]
+    PngEncodingError(#[from] png::EncodingError),
+    /// Encapsulates a [png::DecodingError] from the [png] crate, returned when streaming a sheet from a PNG image.
+    #[error("internal png decoding error")]
+    PngDecodingError(#[from] png::DecodingError),
+    /// Encapsulates an [std::io::Error], returned when reading or writing a file fails.
+    #[error("io error")]
+    IoError(#[from] std::io::Error),
 }
 
 /// Type alias for `Result<T, sheety::Error>`
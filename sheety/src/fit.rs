@@ -0,0 +1,55 @@
+/// A point of a [Sprite][crate::Sprite] that stays in place when fitting it to another size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Anchor {
+    /// The top left corner.
+    TopLeft,
+    /// The middle of the top edge.
+    Top,
+    /// The top right corner.
+    TopRight,
+    /// The middle of the left edge.
+    Left,
+    /// The center.
+    Center,
+    /// The middle of the right edge.
+    Right,
+    /// The bottom left corner.
+    BottomLeft,
+    /// The middle of the bottom edge, e.g. the feet of a character.
+    Bottom,
+    /// The bottom right corner.
+    BottomRight,
+    /// A custom pivot, in coordinates relative to the size of the [Sprite][crate::Sprite]:
+    /// `(0.0, 0.0)` is the top left corner, and `(1.0, 1.0)` is the bottom right corner.
+    Pivot(f32, f32),
+}
+
+impl Anchor {
+    /// Gets the coordinates of this [Anchor], relative to the size of the [Sprite][crate::Sprite].
+    pub fn relative(&self) -> (f32, f32) {
+        match self {
+            Self::TopLeft => (0.0, 0.0),
+            Self::Top => (0.5, 0.0),
+            Self::TopRight => (1.0, 0.0),
+            Self::Left => (0.0, 0.5),
+            Self::Center => (0.5, 0.5),
+            Self::Right => (1.0, 0.5),
+            Self::BottomLeft => (0.0, 1.0),
+            Self::Bottom => (0.5, 1.0),
+            Self::BottomRight => (1.0, 1.0),
+            Self::Pivot(x, y) => (*x, *y),
+        }
+    }
+}
+
+/// Reprensents how to fit a [Sprite][crate::Sprite] to a cell size it doesn't have, see
+/// [Sprite::fit][crate::Sprite::fit].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fit {
+    /// Pads or crops the [Sprite][crate::Sprite] around the [Anchor], which ends up at the same relative
+    /// position in the new cell.
+    Anchor(Anchor),
+    /// Scales the [Sprite][crate::Sprite] so that it fits in the new cell while keeping its aspect ratio, and then
+    /// pads the remaining space around the [Anchor]. Uses nearest neighbour filtering, so that pixel art stays sharp.
+    Scale(Anchor),
+}
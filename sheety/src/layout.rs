@@ -0,0 +1,125 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, Result},
+    sprite_cell::SpriteCell,
+    utils::IVec2,
+    FrameInfo, SpriteSheet,
+};
+
+/// Describes the layout of a [SpriteSheet] without its pixels: its size, the size of its cells, and which cells
+/// hold a frame. With the `serde` feature, it can be serialized, e.g. to cache layouts or to describe a sheet
+/// to a game engine.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SheetLayout {
+    /// The size of the [SpriteSheet], in cells.
+    pub size: IVec2,
+    /// The size of the cells, in pixels.
+    pub cell_size: IVec2,
+    /// All cells, line after line; [None] for empty cells.
+    pub cells: Vec<Option<FrameLayout>>,
+}
+
+/// Describes a non-empty cell of a [SheetLayout].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FrameLayout {
+    /// The index of the frame, only counting non-empty cells, line after line, like
+    /// [SpriteSheet::into_unordered].
+    pub index: usize,
+    /// The position of the top left corner of the frame in the image of the sheet, in pixels.
+    pub position: IVec2,
+    /// What is known about the frame besides its pixels.
+    pub info: FrameInfo,
+}
+
+impl SheetLayout {
+    /// Gets the amount of non-empty cells.
+    pub fn frame_count(&self) -> usize {
+        self.frames().count()
+    }
+
+    /// Returns an iterator of the [FrameLayout]s of the non-empty cells, line after line.
+    pub fn frames(&self) -> impl Iterator<Item = &FrameLayout> {
+        self.cells.iter().flatten()
+    }
+
+    /// Returns the [FrameLayout] of the cell at `coords`, or [None] if it is empty. Cells missing from
+    /// [SheetLayout::cells], e.g. in a layout deserialized from a truncated file, are empty.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::OutOfBounds] if `coords` are out of bounds.
+    pub fn get_cell(&self, coords: IVec2) -> Result<Option<&FrameLayout>> {
+        if coords.0 >= self.size.0 || coords.1 >= self.size.1 {
+            return Err(Error::OutOfBounds {
+                max: self.size,
+                provided: coords,
+            });
+        }
+
+        Ok(self
+            .cells
+            .get(coords.1 * self.size.0 + coords.0)
+            .and_then(Option::as_ref))
+    }
+
+    /// Names the frames of this [SheetLayout], in the order of [SheetLayout::frames].
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::MismatchedLength] if there isn't exactly one name per frame.
+    pub fn with_names<I>(mut self, names: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let names: Vec<String> = names.into_iter().collect();
+
+        if names.len() != self.frame_count() {
+            return Err(Error::MismatchedLength {
+                required: self.frame_count(),
+                provided: names.len(),
+            });
+        }
+
+        for (frame, name) in self.cells.iter_mut().flatten().zip(names) {
+            frame.info.name = Some(name);
+        }
+
+        Ok(self)
+    }
+}
+
+impl SpriteSheet {
+    /// Describes the layout of this [SpriteSheet], see [SheetLayout]. Frames keep the [FrameInfo] of their
+    /// [Sprite][crate::Sprite]s.
+    pub fn layout(&self) -> SheetLayout {
+        let (width, cell_size) = (self.size().0, self.cell_size());
+        let mut index = 0;
+
+        let cells = self
+            .cells()
+            .enumerate()
+            .map(|(i, cell)| match cell {
+                SpriteCell::Sprite(sprite) => {
+                    index += 1;
+
+                    Some(FrameLayout {
+                        index: index - 1,
+                        position: ((i % width) * cell_size.0, (i / width) * cell_size.1),
+                        info: FrameInfo::from(sprite),
+                    })
+                }
+                SpriteCell::Empty => None,
+            })
+            .collect();
+
+        SheetLayout {
+            size: self.size(),
+            cell_size,
+            cells,
+        }
+    }
+}
@@ -7,7 +7,8 @@
 //!
 //! This is one of this crates examples - it concatenates to existing sprite sheets into a single.
 //!
-//! ```
+//! ```no_run
+//! # use sheety::{Distribution, SpriteSheet};
 //! SpriteSheet::concat(
 //!     vec![
 //!         SpriteSheet::load_cell_size("sheety/tests/machete_idle.png", (256, 256))
@@ -30,18 +31,50 @@
 #![warn(missing_docs)]
 
 extern crate image;
+extern crate png;
 extern crate thiserror;
 
+mod background;
+mod boxes;
+mod diff;
+mod directional;
 mod distribution;
 pub mod error;
+mod fit;
+mod layout;
+mod lint;
+mod markers;
+mod mask;
+mod metadata;
+mod nine_slice;
+mod onion_skin;
+mod outline;
+mod preview;
+mod project;
 mod sprite;
 mod sprite_cell;
 mod sprite_sheet;
+mod streaming;
 mod unordered_sprite_sheet;
 mod utils;
 
+pub use background::Background;
+pub use boxes::{BoxKind, CollisionBox};
+pub use diff::{CellChange, CellDiff, SheetDiff};
+pub use directional::{Direction, DirectionalSheet};
 pub use distribution::Distribution;
-pub use sprite::Sprite;
+pub use fit::{Anchor, Fit};
+pub use layout::{FrameLayout, SheetLayout};
+pub use lint::{Edge, LintOptions, LintWarning};
+pub use mask::CollisionMask;
+pub use metadata::{AnimationTag, FrameInfo, SheetMetadata};
+pub use nine_slice::{NineSlice, SliceMode};
+pub use onion_skin::OnionSkin;
+pub use outline::Outline;
+pub use preview::PreviewOptions;
+pub use sprite::{Sprite, SpriteSource};
 pub use sprite_cell::SpriteCell;
-pub use sprite_sheet::SpriteSheet;
-pub use unordered_sprite_sheet::UnorderedSpriteSheet;
+pub use sprite_sheet::{
+    Column, Columns, IntoIterCells, IterCells, IterCellsMut, Remainder, Rows, RowsMut, SpriteSheet,
+};
+pub use unordered_sprite_sheet::{UnorderedSpriteSheet, MAX_RETIME_FACTOR};
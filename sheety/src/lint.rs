@@ -0,0 +1,221 @@
+use std::fmt;
+
+use crate::{sprite_cell::SpriteCell, utils::IVec2, SpriteSheet};
+
+/// What to look for when checking a [SpriteSheet] for mistakes, see [SpriteSheet::lint].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LintOptions {
+    /// Pixels are visible if their alpha is greater than the threshold, `0` makes every pixel that isn't fully
+    /// transparent visible.
+    pub threshold: u8,
+    /// Frames with fewer visible pixels than this are likely meant to be empty.
+    pub min_pixels: usize,
+    /// How much the width or the height of the bounds of a frame can differ from the ones of most frames, relative
+    /// to them, e.g. `0.5` flags frames less than half or more than one and a half times as wide as most.
+    pub bounds_tolerance: f32,
+}
+
+impl Default for LintOptions {
+    fn default() -> Self {
+        Self {
+            threshold: 0,
+            min_pixels: 4,
+            bounds_tolerance: 0.5,
+        }
+    }
+}
+
+/// An edge of a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Edge {
+    /// The top edge.
+    Top,
+    /// The right edge.
+    Right,
+    /// The bottom edge.
+    Bottom,
+    /// The left edge.
+    Left,
+}
+
+impl fmt::Display for Edge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Top => "top",
+            Self::Right => "right",
+            Self::Bottom => "bottom",
+            Self::Left => "left",
+        })
+    }
+}
+
+/// A likely mistake found in a [SpriteSheet], e.g. because it was loaded with the wrong size.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LintWarning {
+    /// The image of the sheet isn't made of whole cells, so pixels were left out when it was sliced.
+    IndivisibleImage {
+        /// The size of the image, in pixels.
+        image_size: IVec2,
+        /// The size of the cells, in pixels.
+        cell_size: IVec2,
+    },
+    /// Visible pixels touch edges of a cell, so its frame may be cut in half, or overflow into other cells.
+    TouchingEdges {
+        /// The coordinates of the cell.
+        coords: IVec2,
+        /// The edges touched, clockwise from the top one.
+        edges: Vec<Edge>,
+    },
+    /// A cell has a few visible pixels only, so it is likely a piece of a frame from another cell, or stray pixels.
+    AlmostEmpty {
+        /// The coordinates of the cell.
+        coords: IVec2,
+        /// The amount of visible pixels of the cell.
+        pixels: usize,
+    },
+    /// The bounds of the visible pixels of a frame are much smaller or bigger than the ones of most frames.
+    InconsistentBounds {
+        /// The coordinates of the cell.
+        coords: IVec2,
+        /// The size of the bounds of the frame, in pixels.
+        size: IVec2,
+        /// The median size of the bounds of the frames, in pixels.
+        median: IVec2,
+    },
+}
+
+impl SpriteSheet {
+    /// Checks this [SpriteSheet] for likely mistakes according to `options`: cells whose visible pixels touch their
+    /// edges, cells with a few visible pixels only, and frames whose bounds don't look like the others. See
+    /// [SpriteSheet::lint_image_size] to check that the image was made of whole cells.
+    pub fn lint(&self, options: &LintOptions) -> Vec<LintWarning> {
+        let (width, cell_size) = (self.size().0, self.cell_size());
+        let mut warnings = Vec::new();
+        let mut bounds = Vec::new();
+
+        for (i, cell) in self.cells().enumerate() {
+            let SpriteCell::Sprite(sprite) = cell else {
+                continue;
+            };
+
+            let coords = (i % width, i / width);
+            let mut pixels = 0;
+            let (mut min, mut max) = ((usize::MAX, usize::MAX), (0, 0));
+
+            for (y, row) in sprite.raw_rows().enumerate() {
+                for (x, pixel) in row.chunks_exact(4).enumerate() {
+                    if pixel[3] > options.threshold {
+                        pixels += 1;
+                        min = (min.0.min(x), min.1.min(y));
+                        max = (max.0.max(x + 1), max.1.max(y + 1));
+                    }
+                }
+            }
+
+            if pixels == 0 {
+                continue;
+            }
+
+            let edges: Vec<Edge> = [
+                (min.1 == 0, Edge::Top),
+                (max.0 == cell_size.0, Edge::Right),
+                (max.1 == cell_size.1, Edge::Bottom),
+                (min.0 == 0, Edge::Left),
+            ]
+            .into_iter()
+            .filter_map(|(touching, edge)| touching.then_some(edge))
+            .collect();
+
+            if !edges.is_empty() {
+                warnings.push(LintWarning::TouchingEdges { coords, edges });
+            }
+
+            if pixels < options.min_pixels {
+                warnings.push(LintWarning::AlmostEmpty { coords, pixels });
+            } else {
+                bounds.push((coords, (max.0 - min.0, max.1 - min.1)));
+            }
+        }
+
+        // A couple of frames don't tell what most frames look like.
+        if bounds.len() >= 3 {
+            let median = |axis: fn(&IVec2) -> usize| {
+                let mut values: Vec<usize> = bounds.iter().map(|(_, size)| axis(size)).collect();
+                values.sort_unstable();
+                values[values.len() / 2]
+            };
+            let median = (median(|size| size.0), median(|size| size.1));
+
+            let off = |value: usize, median: usize| {
+                (value as f32 - median as f32).abs() > median as f32 * options.bounds_tolerance
+            };
+
+            warnings.extend(
+                bounds
+                    .into_iter()
+                    .filter(|(_, size)| off(size.0, median.0) || off(size.1, median.1))
+                    .map(|(coords, size)| LintWarning::InconsistentBounds {
+                        coords,
+                        size,
+                        median,
+                    }),
+            );
+        }
+
+        warnings
+    }
+
+    /// Checks that an image of `image_size` pixels is made of the whole cells of this [SpriteSheet], which was sliced
+    /// from it, e.g. with [SpriteSheet::load_cell_size]. Returns [LintWarning::IndivisibleImage] if pixels were left
+    /// out.
+    pub fn lint_image_size(&self, image_size: IVec2) -> Option<LintWarning> {
+        let (size, cell_size) = (self.size(), self.cell_size());
+
+        (image_size != (size.0 * cell_size.0, size.1 * cell_size.1)).then_some(
+            LintWarning::IndivisibleImage {
+                image_size,
+                cell_size,
+            },
+        )
+    }
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IndivisibleImage {
+                image_size,
+                cell_size,
+            } => write!(
+                f,
+                "the image is {}x{} pixels, which isn't made of whole cells of {}x{} pixels",
+                image_size.0, image_size.1, cell_size.0, cell_size.1
+            ),
+            Self::TouchingEdges { coords, edges } => {
+                let edges: Vec<String> = edges.iter().map(Edge::to_string).collect();
+
+                write!(
+                    f,
+                    "cell {}x{}: visible pixels touch the {} edge(s), the sprite may be cut",
+                    coords.0,
+                    coords.1,
+                    edges.join(", ")
+                )
+            }
+            Self::AlmostEmpty { coords, pixels } => write!(
+                f,
+                "cell {}x{}: only {pixels} visible pixel(s), the cell may be meant to be empty",
+                coords.0, coords.1
+            ),
+            Self::InconsistentBounds {
+                coords,
+                size,
+                median,
+            } => write!(
+                f,
+                "cell {}x{}: the sprite is {}x{} pixels, unlike most sprites, which are about {}x{} pixels",
+                coords.0, coords.1, size.0, size.1, median.0, median.1
+            ),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use image::Rgba;
+
+use crate::{
+    error::{Error, Result},
+    sprite_cell::SpriteCell,
+    utils::IVec2,
+    BoxKind, CollisionBox, Sprite, SpriteSheet,
+};
+
+impl SpriteSheet {
+    /// Sets the pivots of the [Sprite]s of this [SpriteSheet] from a companion sheet of `markers`, aligned cell for
+    /// cell with it, where pivots are painted as pixels of `colour`. Each pivot is the center of the first pixel of
+    /// `colour` in its cell, line after line. [Sprite]s whose cell has no marker keep their pivot.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::MismatchedSheetSize] if `markers` doesn't have the size and cell size of this
+    ///   [SpriteSheet].
+    pub fn set_pivots_from_markers(
+        &mut self,
+        markers: &SpriteSheet,
+        colour: Rgba<u8>,
+    ) -> Result<()> {
+        self.check_companion(markers)?;
+
+        for (cell, marker) in self.cells_mut().zip(markers.cells()) {
+            if let (SpriteCell::Sprite(sprite), SpriteCell::Sprite(marker)) = (cell, marker) {
+                if let Some((x, y)) = find_marker(marker, colour) {
+                    sprite.set_pivot(Some((x as f32 + 0.5, y as f32 + 0.5)));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets the [CollisionBox]es of the [Sprite]s of this [SpriteSheet] from a companion sheet of `markers`,
+    /// aligned cell for cell with it, where hurtboxes are painted in `hurtbox` and hitboxes in `hitbox`. Each
+    /// connected area of a colour becomes a box that bounds it, so touching boxes of the same kind are merged.
+    /// [Sprite]s whose cell is empty in `markers` keep their boxes, others have them replaced.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::MismatchedSheetSize] if `markers` doesn't have the size and cell size of this
+    ///   [SpriteSheet].
+    pub fn set_boxes_from_markers(
+        &mut self,
+        markers: &SpriteSheet,
+        hurtbox: Rgba<u8>,
+        hitbox: Rgba<u8>,
+    ) -> Result<()> {
+        self.check_companion(markers)?;
+
+        for (cell, marker) in self.cells_mut().zip(markers.cells()) {
+            if let (SpriteCell::Sprite(sprite), SpriteCell::Sprite(marker)) = (cell, marker) {
+                let mut boxes = find_boxes(marker, BoxKind::Hurtbox, hurtbox);
+                boxes.extend(find_boxes(marker, BoxKind::Hitbox, hitbox));
+
+                sprite.set_boxes(boxes);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that `companion` has the size and cell size of this [SpriteSheet].
+    pub(crate) fn check_companion(&self, companion: &SpriteSheet) -> Result<()> {
+        if self.size() != companion.size() || self.cell_size() != companion.cell_size() {
+            return Err(Error::MismatchedSheetSize {
+                required: self.size(),
+                required_cell_size: self.cell_size(),
+                provided: companion.size(),
+                provided_cell_size: companion.cell_size(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Finds the first pixel of `colour` in `sprite`, line after line.
+fn find_marker(sprite: &Sprite, colour: Rgba<u8>) -> Option<IVec2> {
+    sprite.raw_rows().enumerate().find_map(|(y, row)| {
+        row.chunks_exact(4)
+            .position(|px| px == colour.0)
+            .map(|x| (x, y))
+    })
+}
+
+/// Finds the boxes bounding the connected areas of pixels of `colour` in `sprite`, in the order of their first
+/// pixel, line after line.
+fn find_boxes(sprite: &Sprite, kind: BoxKind, colour: Rgba<u8>) -> Vec<CollisionBox> {
+    let (width, height) = sprite.size();
+    let mut marked: Vec<bool> = sprite
+        .raw_rows()
+        .flat_map(|row| row.chunks_exact(4).map(|px| px == colour.0))
+        .collect();
+
+    let mut boxes = Vec::new();
+
+    for start in 0..marked.len() {
+        if !marked[start] {
+            continue;
+        }
+
+        marked[start] = false;
+
+        let (mut min, mut max) = ((width, height), (0, 0));
+        let mut stack = vec![start];
+
+        while let Some(i) = stack.pop() {
+            let (x, y) = (i % width, i / width);
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+
+            let neighbours = [
+                (x > 0).then(|| i - 1),
+                (x + 1 < width).then_some(i + 1),
+                (y > 0).then(|| i - width),
+                (y + 1 < height).then_some(i + width),
+            ];
+
+            for neighbour in neighbours.into_iter().flatten() {
+                if marked[neighbour] {
+                    marked[neighbour] = false;
+                    stack.push(neighbour);
+                }
+            }
+        }
+
+        boxes.push(CollisionBox {
+            kind,
+            position: min,
+            size: (max.0 - min.0 + 1, max.1 - min.1 + 1),
+        });
+    }
+
+    boxes
+}
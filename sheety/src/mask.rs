@@ -0,0 +1,168 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use image::RgbaImage;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{
+    error::{Error, Result},
+    sprite_cell::SpriteCell,
+    utils::IVec2,
+    Sprite, SpriteSheet,
+};
+
+/// A 1-bit mask of the solid pixels of a [Sprite], e.g. for pixel-perfect collisions, see
+/// [Sprite::collision_mask].
+///
+/// Its bits are packed line after line, the first pixel of a line in the most significant bit of its first byte,
+/// and each line is padded to a whole byte.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CollisionMask {
+    size: IVec2,
+    bits: Vec<u8>,
+}
+
+impl CollisionMask {
+    /// Gets the size of this [CollisionMask], in pixels.
+    pub fn size(&self) -> IVec2 {
+        self.size
+    }
+
+    /// Gets the amount of bytes of each line of this [CollisionMask].
+    pub fn line_len(&self) -> usize {
+        self.size.0.div_ceil(8)
+    }
+
+    /// Returns `true` if the pixel at `coords` is solid.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::OutOfBounds] if `coords` are out of bounds.
+    pub fn get(&self, coords: IVec2) -> Result<bool> {
+        if coords.0 >= self.size.0 || coords.1 >= self.size.1 {
+            return Err(Error::OutOfBounds {
+                max: self.size,
+                provided: coords,
+            });
+        }
+
+        let byte = self.bits[coords.1 * self.line_len() + coords.0 / 8];
+        Ok(byte & (0x80 >> (coords.0 % 8)) != 0)
+    }
+
+    /// Gets the bit-packed lines of this [CollisionMask].
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bits
+    }
+
+    /// Makes an image of this [CollisionMask], where solid pixels are opaque white, and others are fully
+    /// transparent.
+    pub fn to_image(&self) -> RgbaImage {
+        RgbaImage::from_fn(self.size.0 as u32, self.size.1 as u32, |x, y| {
+            match self.get((x as usize, y as usize)) {
+                Ok(true) => image::Rgba([255, 255, 255, 255]),
+                _ => image::Rgba([0, 0, 0, 0]),
+            }
+        })
+    }
+}
+
+impl Sprite {
+    /// Makes the [CollisionMask] of this [Sprite], where pixels are solid if their alpha is greater than
+    /// `threshold`: `0` makes every pixel that isn't fully transparent solid.
+    /// With the `rayon` feature, lines of pixels are scanned in parallel.
+    pub fn collision_mask(&self, threshold: u8) -> CollisionMask {
+        let size = self.size();
+
+        let lines: Vec<Vec<u8>> = self
+            .par_raw_rows()
+            .map(|row| {
+                let mut line = vec![0; size.0.div_ceil(8)];
+
+                for (x, px) in row.chunks_exact(4).enumerate() {
+                    if px[3] > threshold {
+                        line[x / 8] |= 0x80 >> (x % 8);
+                    }
+                }
+
+                line
+            })
+            .collect();
+
+        CollisionMask {
+            size,
+            bits: lines.concat(),
+        }
+    }
+}
+
+impl SpriteSheet {
+    /// Makes a sheet of the [CollisionMask]s of the [Sprite]s of this [SpriteSheet], aligned with its grid, see
+    /// [CollisionMask::to_image]. Empty cells stay empty, and masks keep the names,
+    /// [SpriteSource][crate::SpriteSource]s, pivots and [CollisionBox][crate::CollisionBox]es of their [Sprite]s.
+    pub fn mask_sheet(&self, threshold: u8) -> SpriteSheet {
+        let mut masks = SpriteSheet::new(self.size(), self.cell_size());
+
+        for (mask, cell) in masks.cells_mut().zip(self.cells()) {
+            if let SpriteCell::Sprite(sprite) = cell {
+                let image = sprite.collision_mask(threshold).to_image();
+                *mask = SpriteCell::Sprite(sprite.derive(image, |point| point));
+            }
+        }
+
+        masks
+    }
+
+    /// Writes the [CollisionMask]s of the [Sprite]s of this [SpriteSheet] into `writer`, as a compact binary blob.
+    /// Only non-empty cells have a mask, in the order of [SpriteSheet::into_unordered].
+    ///
+    /// The blob starts with the width and the height of the cells and the amount of masks, as little endian
+    /// `u32`s, followed by the bytes of each mask, see [CollisionMask::as_bytes].
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::IoError] if writing to `writer` fails.
+    pub fn write_masks<W>(&self, mut writer: W, threshold: u8) -> Result<()>
+    where
+        W: Write,
+    {
+        let sprites: Vec<&Sprite> = self
+            .cells()
+            .filter_map(|cell| match cell {
+                SpriteCell::Sprite(sprite) => Some(sprite),
+                SpriteCell::Empty => None,
+            })
+            .collect();
+
+        for value in [self.cell_size().0, self.cell_size().1, sprites.len()] {
+            writer.write_all(&(value as u32).to_le_bytes())?;
+        }
+
+        for sprite in sprites {
+            writer.write_all(sprite.collision_mask(threshold).as_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    /// Saves the [CollisionMask]s of the [Sprite]s of this [SpriteSheet] to the disk, see
+    /// [SpriteSheet::write_masks].
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::IoError] if the file could not be created or written to.
+    pub fn save_masks<P>(&self, path: P, threshold: u8) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_masks(&mut writer, threshold)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+}
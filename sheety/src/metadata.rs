@@ -0,0 +1,664 @@
+use std::{collections::BTreeMap, fmt, ops::Range, path::PathBuf, str::FromStr};
+
+use image::RgbaImage;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, Result},
+    sprite_cell::SpriteCell,
+    unordered_sprite_sheet::UnorderedSpriteSheet,
+    utils::IVec2,
+    CollisionBox, NineSlice, Outline, Sprite, SpriteSheet, SpriteSource,
+};
+
+/// The keyword of the PNG text chunk holding the [SheetMetadata] of a sheet.
+pub(crate) const METADATA_KEYWORD: &str = "sheety";
+
+/// The version of the text format of [SheetMetadata], written on its first line.
+const METADATA_VERSION: u32 = 1;
+
+/// What a sheet knows about one of its frames besides its pixels: the name, [SpriteSource], pivot,
+/// [CollisionBox]es, [Outline]s and [NineSlice] borders of its [Sprite]. Shared by [SheetMetadata] and
+/// [FrameLayout][crate::FrameLayout].
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FrameInfo {
+    /// The name of the frame, if it has one.
+    pub name: Option<String>,
+    /// Where the pixels of the frame come from, if known.
+    pub source: Option<SpriteSource>,
+    /// The pivot of the frame, in pixels from its top left corner, see [Sprite::pivot].
+    pub pivot: Option<(f32, f32)>,
+    /// The [CollisionBox]es of the frame, in pixels from its top left corner.
+    pub boxes: Vec<CollisionBox>,
+    /// The [Outline]s of the frame, in pixels from its top left corner.
+    pub outlines: Vec<Outline>,
+    /// The [NineSlice] borders of the frame, if it has some.
+    pub nine_slice: Option<NineSlice>,
+}
+
+impl FrameInfo {
+    /// Returns `true` if nothing is known about the frame.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Gives what is known about the frame to `sprite`, leaving the rest of it as is.
+    pub(crate) fn describe(&self, sprite: &mut Sprite) {
+        if let Some(name) = &self.name {
+            sprite.set_name(Some(name));
+        }
+
+        if let Some(source) = &self.source {
+            sprite.set_source(Some(source.clone()));
+        }
+
+        if let Some(pivot) = self.pivot {
+            sprite.set_pivot(Some(pivot));
+        }
+
+        if !self.boxes.is_empty() {
+            sprite.set_boxes(self.boxes.clone());
+        }
+
+        if !self.outlines.is_empty() {
+            sprite.set_outlines(self.outlines.clone());
+        }
+
+        if let Some(nine_slice) = self.nine_slice {
+            sprite.set_nine_slice(Some(nine_slice));
+        }
+    }
+}
+
+impl From<&Sprite> for FrameInfo {
+    fn from(sprite: &Sprite) -> Self {
+        Self {
+            name: sprite.name().map(str::to_owned),
+            source: sprite.source().cloned(),
+            pivot: sprite.pivot(),
+            boxes: sprite.boxes().to_vec(),
+            outlines: sprite.outlines().to_vec(),
+            nine_slice: sprite.nine_slice(),
+        }
+    }
+}
+
+/// A named range of frames of a sheet, e.g. the frames of a walk cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnimationTag {
+    /// The name of the animation.
+    pub name: String,
+    /// The frames of the animation, as indices in [SheetMetadata::frames].
+    pub frames: Range<usize>,
+}
+
+impl AnimationTag {
+    /// Moves this [AnimationTag] to the frames of a new sheet, e.g. once frames were reordered, repeated or
+    /// deleted. `origins` gives the frame of the old sheet each new frame comes from, if any. The tag then spans
+    /// the first run of consecutive new frames that come from its own frames, or is [None] if none of them do.
+    pub fn remap<I>(&self, origins: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Option<usize>>,
+    {
+        let tagged = |origin: &Option<usize>| origin.is_some_and(|i| self.frames.contains(&i));
+
+        let mut frames = origins
+            .into_iter()
+            .enumerate()
+            .skip_while(|(_, origin)| !tagged(origin))
+            .take_while(|(_, origin)| tagged(origin))
+            .map(|(i, _)| i);
+
+        let start = frames.next()?;
+        let end = frames.last().unwrap_or(start) + 1;
+
+        Some(Self {
+            name: self.name.clone(),
+            frames: start..end,
+        })
+    }
+}
+
+/// Describes how to read a [SpriteSheet] back from its image: its grid, which cells hold its frames and in which
+/// order, the names, [SpriteSource]s, pivots, [CollisionBox]es, [Outline]s and [NineSlice] borders of its frames,
+/// and its [AnimationTag]s. Saving a [SpriteSheet] as a PNG image embeds it in a text chunk, see [SpriteSheet::save_png]
+/// and [SpriteSheet::load_with_metadata].
+///
+/// Its text format has one `key value` entry per line, e.g.:
+///
+/// ```text
+/// sheety 1
+/// size 4x3
+/// cell_size 128x96
+/// frames 0-9,11
+/// name 0 walk_00
+/// source 0 2x0 sprites/walk.png
+/// pivot 0 64,90.5
+/// box 0 hurtbox 40x12 48x84
+/// outline 0 40,8 88,8 88,96 40,96 ; 0,1,2 0,2,3
+/// nineslice 0 8,8,8,12
+/// tag 0-4 walk
+/// ```
+///
+/// Ranges exclude their end, and tags are given as ranges of frames followed by a name. Names, sources, pivots,
+/// boxes, outlines and nine-slice borders are given by frame, sources with the coordinates of their cell followed
+/// by their path, if any, pivots in pixels from the top left corner of the frame, boxes with their kind, position
+/// and size, outlines with their vertices, followed by their triangles, if any, and nine-slice borders as
+/// `left,top,right,bottom`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SheetMetadata {
+    /// The size of the sheet, in cells.
+    pub size: IVec2,
+    /// The size of the cells, in pixels.
+    pub cell_size: IVec2,
+    /// The cells holding the frames of the sheet, in playing order, as indices of cells line after line.
+    /// Unlike [SpriteSheet::into_unordered], fully transparent frames are kept.
+    pub frames: Vec<usize>,
+    /// The [FrameInfo] of the frames that have some, by index in [SheetMetadata::frames].
+    pub info: BTreeMap<usize, FrameInfo>,
+    /// The [AnimationTag]s of the sheet.
+    pub tags: Vec<AnimationTag>,
+}
+
+impl SheetMetadata {
+    /// Adds an [AnimationTag] named `name`, spanning `frames`.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::OutOfBounds] if `frames` isn't a range of [SheetMetadata::frames].
+    /// - Will return [Error::InvalidMetadata] if `name` spans several lines.
+    pub fn with_tag<S>(mut self, name: S, frames: Range<usize>) -> Result<Self>
+    where
+        S: Into<String>,
+    {
+        let name = name.into();
+
+        if name.contains(['\n', '\r']) {
+            return Err(Error::InvalidMetadata(format!(
+                "tag name `{name}` spans several lines"
+            )));
+        }
+
+        if frames.start > frames.end || frames.end > self.frames.len() {
+            return Err(Error::OutOfBounds {
+                max: (self.frames.len(), 0),
+                provided: (frames.end, 0),
+            });
+        }
+
+        self.tags.push(AnimationTag { name, frames });
+
+        Ok(self)
+    }
+
+    /// Gets the [AnimationTag] named `name`, if there is one.
+    pub fn tag(&self, name: &str) -> Option<&AnimationTag> {
+        self.tags.iter().find(|tag| tag.name == name)
+    }
+
+    /// Takes the frames of `sheet` in the order of [SheetMetadata::frames]. Frames in empty cells are fully
+    /// transparent [Sprite]s.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::InvalidMetadata] if `sheet` doesn't have the size and cell size of this
+    ///   [SheetMetadata].
+    /// - Will return [Error::EmptyUnorderedSpriteSheet] if there are no frames.
+    pub fn unordered(&self, sheet: &SpriteSheet) -> Result<UnorderedSpriteSheet> {
+        if sheet.size() != self.size || sheet.cell_size() != self.cell_size {
+            return Err(Error::InvalidMetadata(format!(
+                "the sheet is {:?} cells of {:?} pixels, not {:?} cells of {:?} pixels",
+                sheet.size(),
+                sheet.cell_size(),
+                self.size,
+                self.cell_size,
+            )));
+        }
+
+        let cells: Vec<&SpriteCell> = sheet.cells().collect();
+        let mut empty = None;
+
+        UnorderedSpriteSheet::new(
+            self.frames
+                .iter()
+                .enumerate()
+                .map(|(frame, i)| {
+                    let mut sprite = match cells[*i] {
+                        SpriteCell::Sprite(sprite) => sprite.clone(),
+                        SpriteCell::Empty => empty
+                            .get_or_insert_with(|| {
+                                Sprite::from(RgbaImage::new(
+                                    self.cell_size.0 as u32,
+                                    self.cell_size.1 as u32,
+                                ))
+                            })
+                            .clone(),
+                    };
+
+                    if let Some(info) = self.info.get(&frame) {
+                        info.describe(&mut sprite);
+                    }
+
+                    sprite
+                })
+                .collect(),
+        )
+    }
+
+    /// Gives the [FrameInfo] of the frames to the [Sprite]s of `sheet`, which should match this [SheetMetadata].
+    pub(crate) fn apply_to(&self, sheet: &mut SpriteSheet) {
+        let mut cells: Vec<&mut SpriteCell> = sheet.cells_mut().collect();
+
+        for (frame, info) in self.info.iter() {
+            if let SpriteCell::Sprite(sprite) = &mut cells[self.frames[*frame]] {
+                info.describe(sprite);
+            }
+        }
+    }
+
+    /// Checks that the grid of this [SheetMetadata] makes an image of `image_size` pixels.
+    pub(crate) fn check_image_size(&self, image_size: IVec2) -> Result<()> {
+        let (size, cell_size) = (self.size, self.cell_size);
+        let expected = size
+            .0
+            .checked_mul(cell_size.0)
+            .zip(size.1.checked_mul(cell_size.1));
+
+        if expected != Some(image_size) {
+            return Err(Error::InvalidMetadata(format!(
+                "{size:?} cells of {cell_size:?} pixels don't match an image of {image_size:?} pixels"
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn validate(self) -> Result<Self> {
+        let cell_count = cell_count(self.size)?;
+
+        if let Some(i) = self.frames.iter().find(|i| **i >= cell_count) {
+            return Err(Error::InvalidMetadata(format!(
+                "frame cell `{i}` is out of bounds (max: `{cell_count}`)"
+            )));
+        }
+
+        if let Some(frame) = self.info.keys().find(|frame| **frame >= self.frames.len()) {
+            return Err(Error::InvalidMetadata(format!(
+                "frame `{frame}` has a name, a source, a pivot, boxes, outlines or nine-slice borders, but doesn't \
+                exist (max: `{}`)",
+                self.frames.len()
+            )));
+        }
+
+        if let Some((frame, nine_slice)) = self
+            .info
+            .iter()
+            .filter_map(|(frame, info)| Some((frame, info.nine_slice?)))
+            .find(|(_, nine_slice)| !nine_slice.fits(self.cell_size))
+        {
+            return Err(Error::InvalidMetadata(format!(
+                "the nine-slice borders `{nine_slice}` of frame `{frame}` don't fit in its cell"
+            )));
+        }
+
+        if let Some(tag) = self
+            .tags
+            .iter()
+            .find(|tag| tag.frames.start > tag.frames.end || tag.frames.end > self.frames.len())
+        {
+            return Err(Error::InvalidMetadata(format!(
+                "tag `{}` spans frames `{:?}` that don't exist (max: `{}`)",
+                tag.name,
+                tag.frames,
+                self.frames.len()
+            )));
+        }
+
+        Ok(self)
+    }
+}
+
+impl fmt::Display for SheetMetadata {
+    /// Writes this [SheetMetadata] in its text format. Consecutive frames are written as ranges.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{METADATA_KEYWORD} {METADATA_VERSION}")?;
+        writeln!(f, "size {}x{}", self.size.0, self.size.1)?;
+        writeln!(f, "cell_size {}x{}", self.cell_size.0, self.cell_size.1)?;
+        write!(f, "frames ")?;
+
+        let mut frames = self.frames.iter().peekable();
+        let mut first = true;
+
+        while let Some(start) = frames.next() {
+            let mut end = start + 1;
+
+            while frames.next_if_eq(&&end).is_some() {
+                end += 1;
+            }
+
+            if !first {
+                write!(f, ",")?;
+            }
+
+            first = false;
+
+            match end - start {
+                1 => write!(f, "{start}")?,
+                _ => write!(f, "{start}-{end}")?,
+            }
+        }
+
+        writeln!(f)?;
+
+        for (frame, info) in self.info.iter() {
+            if let Some(name) = &info.name {
+                writeln!(f, "name {frame} {}", single_line(name))?;
+            }
+
+            if let Some(source) = &info.source {
+                write!(f, "source {frame} {}x{}", source.cell.0, source.cell.1)?;
+
+                match &source.path {
+                    Some(path) => writeln!(f, " {}", single_line(&path.to_string_lossy()))?,
+                    None => writeln!(f)?,
+                }
+            }
+
+            if let Some(pivot) = info.pivot {
+                writeln!(f, "pivot {frame} {},{}", pivot.0, pivot.1)?;
+            }
+
+            for collision_box in info.boxes.iter() {
+                writeln!(
+                    f,
+                    "box {frame} {} {}x{} {}x{}",
+                    collision_box.kind,
+                    collision_box.position.0,
+                    collision_box.position.1,
+                    collision_box.size.0,
+                    collision_box.size.1
+                )?;
+            }
+
+            for outline in info.outlines.iter() {
+                write!(f, "outline {frame}")?;
+
+                for vertex in outline.vertices.iter() {
+                    write!(f, " {},{}", vertex.0, vertex.1)?;
+                }
+
+                if !outline.triangles.is_empty() {
+                    write!(f, " ;")?;
+
+                    for triangle in outline.triangles.iter() {
+                        write!(f, " {},{},{}", triangle[0], triangle[1], triangle[2])?;
+                    }
+                }
+
+                writeln!(f)?;
+            }
+
+            if let Some(nine_slice) = info.nine_slice {
+                writeln!(f, "nineslice {frame} {nine_slice}")?;
+            }
+        }
+
+        for tag in self.tags.iter() {
+            writeln!(
+                f,
+                "tag {}-{} {}",
+                tag.frames.start, tag.frames.end, tag.name
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for SheetMetadata {
+    type Err = Error;
+
+    /// Parses a [SheetMetadata] from its text format. Unknown keys are ignored.
+    fn from_str(s: &str) -> Result<Self> {
+        let mut lines = s.lines().map(str::trim).filter(|line| !line.is_empty());
+
+        match lines.next().and_then(|line| line.split_once(' ')) {
+            Some((METADATA_KEYWORD, version)) => match version.parse::<u32>() {
+                Ok(version) if version <= METADATA_VERSION => {}
+                _ => {
+                    return Err(Error::InvalidMetadata(format!(
+                        "unsupported version `{version}`"
+                    )))
+                }
+            },
+            _ => {
+                return Err(Error::InvalidMetadata(format!(
+                    "expected a `{METADATA_KEYWORD} <version>` header"
+                )))
+            }
+        }
+
+        let (mut size, mut cell_size, mut frames) = (None, None, None);
+        let mut info = BTreeMap::<usize, FrameInfo>::new();
+        let mut tags = Vec::new();
+
+        for line in lines {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+
+            match key {
+                "size" => size = Some(parse_ivec2(value)?),
+                "cell_size" => cell_size = Some(parse_ivec2(value)?),
+                "frames" => {
+                    let mut list = Vec::new();
+
+                    // Ranges are only expanded once they are known to fit in the sheet.
+                    for item in value.split(',').filter(|item| !item.is_empty()) {
+                        match item.split_once('-') {
+                            Some(_) => list.push(parse_range(item)?),
+                            None => {
+                                let i = parse_number(item)?;
+                                let end = i.checked_add(1).ok_or_else(|| {
+                                    Error::InvalidMetadata(format!("frame cell `{i}` is too large"))
+                                })?;
+
+                                list.push(i..end);
+                            }
+                        }
+                    }
+
+                    frames = Some(list);
+                }
+                "name" => {
+                    let (frame, name) = value.split_once(' ').ok_or_else(|| {
+                        Error::InvalidMetadata(format!("name `{value}` has no frame"))
+                    })?;
+
+                    info.entry(parse_number(frame)?).or_default().name = Some(name.to_owned());
+                }
+                "source" => {
+                    let mut parts = value.splitn(3, ' ');
+                    let frame = parse_number(parts.next().unwrap_or_default())?;
+                    let cell = parse_ivec2(parts.next().unwrap_or_default())?;
+                    let path = parts.next().map(PathBuf::from);
+
+                    info.entry(frame).or_default().source = Some(SpriteSource { path, cell });
+                }
+                "pivot" => {
+                    let (frame, pivot) = value.split_once(' ').ok_or_else(|| {
+                        Error::InvalidMetadata(format!("pivot `{value}` has no frame"))
+                    })?;
+
+                    info.entry(parse_number(frame)?).or_default().pivot = Some(parse_point(pivot)?);
+                }
+                "box" => {
+                    let parts: Vec<&str> = value.split(' ').collect();
+
+                    let [frame, kind, position, size] = parts[..] else {
+                        return Err(Error::InvalidMetadata(format!(
+                            "box `{value}` should be a frame, a kind, a position and a size"
+                        )));
+                    };
+
+                    info.entry(parse_number(frame)?)
+                        .or_default()
+                        .boxes
+                        .push(CollisionBox {
+                            kind: kind.parse()?,
+                            position: parse_ivec2(position)?,
+                            size: parse_ivec2(size)?,
+                        });
+                }
+                "outline" => {
+                    let (vertices, triangles) = value.split_once(';').unwrap_or((value, ""));
+                    let mut vertices = vertices.split_whitespace();
+                    let frame = parse_number(vertices.next().unwrap_or_default())?;
+
+                    let outline = Outline {
+                        vertices: vertices.map(parse_point).collect::<Result<_>>()?,
+                        triangles: triangles
+                            .split_whitespace()
+                            .map(parse_triangle)
+                            .collect::<Result<_>>()?,
+                    };
+
+                    if let Some(i) = outline
+                        .triangles
+                        .iter()
+                        .flatten()
+                        .find(|i| **i >= outline.vertices.len())
+                    {
+                        return Err(Error::InvalidMetadata(format!(
+                            "outline `{value}` has a triangle with vertex `{i}`, which doesn't exist"
+                        )));
+                    }
+
+                    info.entry(frame).or_default().outlines.push(outline);
+                }
+                "nineslice" => {
+                    let (frame, nine_slice) = value.split_once(' ').ok_or_else(|| {
+                        Error::InvalidMetadata(format!("nine-slice `{value}` has no frame"))
+                    })?;
+
+                    info.entry(parse_number(frame)?).or_default().nine_slice =
+                        Some(nine_slice.parse()?);
+                }
+                "tag" => {
+                    let (range, name) = value.split_once(' ').ok_or_else(|| {
+                        Error::InvalidMetadata(format!("tag `{value}` has no name"))
+                    })?;
+
+                    tags.push(AnimationTag {
+                        name: name.to_owned(),
+                        frames: parse_range(range)?,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        let missing = |key: &str| Error::InvalidMetadata(format!("missing `{key}` entry"));
+        let size = size.ok_or_else(|| missing("size"))?;
+        let cell_count = cell_count(size)?;
+        let frames = frames.ok_or_else(|| missing("frames"))?;
+
+        if let Some(range) = frames
+            .iter()
+            .find(|range| range.start > range.end || range.end > cell_count)
+        {
+            return Err(Error::InvalidMetadata(format!(
+                "frame cells `{range:?}` are out of bounds (max: `{cell_count}`)"
+            )));
+        }
+
+        Self {
+            size,
+            cell_size: cell_size.ok_or_else(|| missing("cell_size"))?,
+            frames: frames.into_iter().flatten().collect(),
+            info,
+            tags,
+        }
+        .validate()
+    }
+}
+
+/// Gets the amount of cells of a sheet of `size` cells.
+pub(crate) fn cell_count(size: IVec2) -> Result<usize> {
+    size.0
+        .checked_mul(size.1)
+        .ok_or_else(|| Error::InvalidMetadata(format!("a sheet of {size:?} cells is too large")))
+}
+
+/// Replaces line breaks with spaces, so that `txt` fits on a line of the text format.
+fn single_line(txt: &str) -> String {
+    txt.replace(['\n', '\r'], " ")
+}
+
+fn parse_number(txt: &str) -> Result<usize> {
+    txt.trim()
+        .parse()
+        .map_err(|_| Error::InvalidMetadata(format!("could not parse number `{txt}`")))
+}
+
+fn parse_ivec2(txt: &str) -> Result<IVec2> {
+    let (x, y) = txt
+        .split_once('x')
+        .ok_or_else(|| Error::InvalidMetadata(format!("could not parse size `{txt}`")))?;
+
+    Ok((parse_number(x)?, parse_number(y)?))
+}
+
+fn parse_point(txt: &str) -> Result<(f32, f32)> {
+    let error = || Error::InvalidMetadata(format!("could not parse point `{txt}`"));
+    let (x, y) = txt.split_once(',').ok_or_else(error)?;
+
+    Ok((
+        x.trim().parse().map_err(|_| error())?,
+        y.trim().parse().map_err(|_| error())?,
+    ))
+}
+
+fn parse_triangle(txt: &str) -> Result<[usize; 3]> {
+    let indices: Vec<usize> = txt.split(',').map(parse_number).collect::<Result<_>>()?;
+
+    indices
+        .try_into()
+        .map_err(|_| Error::InvalidMetadata(format!("could not parse triangle `{txt}`")))
+}
+
+fn parse_range(txt: &str) -> Result<Range<usize>> {
+    let (start, end) = txt
+        .split_once('-')
+        .ok_or_else(|| Error::InvalidMetadata(format!("could not parse range `{txt}`")))?;
+
+    Ok(parse_number(start)?..parse_number(end)?)
+}
+
+impl SpriteSheet {
+    /// Describes this [SpriteSheet] with a [SheetMetadata], where its frames are its non-empty cells, line after
+    /// line, with the [FrameInfo] of their [Sprite]s, and without any [AnimationTag].
+    pub fn metadata(&self) -> SheetMetadata {
+        let sprites: Vec<(usize, &Sprite)> = self
+            .cells()
+            .enumerate()
+            .filter_map(|(i, cell)| match cell {
+                SpriteCell::Sprite(sprite) => Some((i, sprite)),
+                SpriteCell::Empty => None,
+            })
+            .collect();
+
+        SheetMetadata {
+            size: self.size(),
+            cell_size: self.cell_size(),
+            frames: sprites.iter().map(|(i, _)| *i).collect(),
+            info: sprites
+                .iter()
+                .enumerate()
+                .map(|(frame, (_, sprite))| (frame, FrameInfo::from(*sprite)))
+                .filter(|(_, info)| !info.is_empty())
+                .collect(),
+            tags: Vec::new(),
+        }
+    }
+}
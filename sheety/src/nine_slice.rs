@@ -0,0 +1,206 @@
+use std::{fmt, str::FromStr};
+
+use image::{GenericImageView, RgbaImage};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, Result},
+    utils::IVec2,
+    Sprite,
+};
+
+/// The borders of a [Sprite] used as a nine-slice, e.g. a UI panel, in pixels from each of its sides. Corners
+/// keep their size when the [Sprite] is resized with [Sprite::nine_slice_to], edges are resized along their length
+/// only, and the centre is resized both ways.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NineSlice {
+    /// The width of the left border.
+    pub left: usize,
+    /// The height of the top border.
+    pub top: usize,
+    /// The width of the right border.
+    pub right: usize,
+    /// The height of the bottom border.
+    pub bottom: usize,
+}
+
+/// How the edges and the centre of a [NineSlice] fill the space between its corners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SliceMode {
+    /// Stretches them, with nearest neighbour filtering, so that pixel art stays sharp.
+    #[default]
+    Stretch,
+    /// Repeats them, starting from the top left corner of each part.
+    Tile,
+}
+
+impl NineSlice {
+    /// Makes a [NineSlice] with borders of `border` pixels on every side.
+    pub fn uniform(border: usize) -> Self {
+        Self {
+            left: border,
+            top: border,
+            right: border,
+            bottom: border,
+        }
+    }
+
+    /// Returns `true` if the borders fit in a [Sprite] of size `size`.
+    pub fn fits(&self, size: IVec2) -> bool {
+        let fits = |a: usize, b: usize, max| a.checked_add(b).is_some_and(|sum| sum <= max);
+
+        fits(self.left, self.right, size.0) && fits(self.top, self.bottom, size.1)
+    }
+
+    /// Moves these borders with `map`, which maps points of a [Sprite] of size `from` to points of a [Sprite] of
+    /// size `to`, by moving the corners of the centre.
+    pub(crate) fn map<F>(&self, from: IVec2, to: IVec2, map: F) -> Self
+    where
+        F: Fn((f32, f32)) -> (f32, f32),
+    {
+        let a = map((self.left as f32, self.top as f32));
+        let b = map((
+            from.0.saturating_sub(self.right) as f32,
+            from.1.saturating_sub(self.bottom) as f32,
+        ));
+
+        let clip = |v: f32, max: usize| f32::clamp(f32::round(v), 0.0, max as f32) as usize;
+
+        Self {
+            left: clip(a.0.min(b.0), to.0),
+            top: clip(a.1.min(b.1), to.1),
+            right: to.0 - clip(a.0.max(b.0), to.0),
+            bottom: to.1 - clip(a.1.max(b.1), to.1),
+        }
+    }
+}
+
+impl fmt::Display for NineSlice {
+    /// Writes the borders as `left,top,right,bottom`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{},{},{},{}",
+            self.left, self.top, self.right, self.bottom
+        )
+    }
+}
+
+impl FromStr for NineSlice {
+    type Err = Error;
+
+    /// Parses borders written as `left,top,right,bottom`, or as a single number for [NineSlice::uniform].
+    fn from_str(s: &str) -> Result<Self> {
+        let borders = s
+            .split(',')
+            .map(|border| border.trim().parse::<usize>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|_| Error::InvalidMetadata(format!("invalid nine-slice borders `{s}`")))?;
+
+        match borders[..] {
+            [border] => Ok(Self::uniform(border)),
+            [left, top, right, bottom] => Ok(Self {
+                left,
+                top,
+                right,
+                bottom,
+            }),
+            _ => Err(Error::InvalidMetadata(format!(
+                "nine-slice borders `{s}` should be `left,top,right,bottom` or a single number"
+            ))),
+        }
+    }
+}
+
+impl Sprite {
+    /// Returns a copy of this [Sprite], resized to `size` as a nine-slice according to its [NineSlice] borders, see
+    /// [Sprite::nine_slice]. Parts of the centre or of the edges that are 0 pixels long are left fully
+    /// transparent.
+    ///
+    /// The copy keeps the name, the [SpriteSource][crate::SpriteSource] and the borders, and its pivot,
+    /// [CollisionBox][crate::CollisionBox]es and [Outline][crate::Outline]s are stretched with the centre, whatever
+    /// the [SliceMode].
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::MissingNineSlice] if this [Sprite] has no [NineSlice] borders.
+    /// - Will return [Error::InvalidNineSlice] if the borders don't fit in this [Sprite], or in `size`.
+    pub fn nine_slice_to(&self, size: IVec2, mode: SliceMode) -> Result<Sprite> {
+        let borders = self.nine_slice().ok_or(Error::MissingNineSlice)?;
+        let from = self.size();
+
+        if let Some(size) = [from, size].into_iter().find(|size| !borders.fits(*size)) {
+            return Err(Error::InvalidNineSlice { borders, size });
+        }
+
+        let view = self.view();
+        let columns: Vec<Option<u32>> = (0..size.0)
+            .map(|x| source(x, size.0, from.0, (borders.left, borders.right), mode))
+            .collect();
+        let lines: Vec<Option<u32>> = (0..size.1)
+            .map(|y| source(y, size.1, from.1, (borders.top, borders.bottom), mode))
+            .collect();
+
+        let pixels = RgbaImage::from_fn(size.0 as u32, size.1 as u32, |x, y| {
+            match (columns[x as usize], lines[y as usize]) {
+                (Some(x), Some(y)) => view.get_pixel(x, y),
+                _ => image::Rgba([0, 0, 0, 0]),
+            }
+        });
+
+        Ok(self.derive(pixels, |(x, y)| {
+            (
+                stretch(x, size.0, from.0, (borders.left, borders.right)),
+                stretch(y, size.1, from.1, (borders.top, borders.bottom)),
+            )
+        }))
+    }
+}
+
+/// Gets the coordinate of the pixel of a [Sprite] `len` pixels long, with `borders` at its start and end, that
+/// ends up at `v` once it is `target` pixels long, along one axis.
+fn source(
+    v: usize,
+    target: usize,
+    len: usize,
+    borders: (usize, usize),
+    mode: SliceMode,
+) -> Option<u32> {
+    let (start, end) = borders;
+    let (centre, stretched) = (len - start - end, target - start - end);
+
+    let source = if v < start {
+        v
+    } else if v >= target - end {
+        len - (target - v)
+    } else if centre == 0 {
+        return None;
+    } else {
+        let offset = v - start;
+
+        start
+            + match mode {
+                SliceMode::Stretch => (2 * offset + 1) * centre / (2 * stretched),
+                SliceMode::Tile => offset % centre,
+            }
+    };
+
+    Some(source as u32)
+}
+
+/// Same as [source], but for points rather than pixels, and always stretching the centre.
+fn stretch(v: f32, target: usize, len: usize, borders: (usize, usize)) -> f32 {
+    let (start, end) = (borders.0 as f32, borders.1 as f32);
+    let (target, len) = (target as f32, len as f32);
+
+    if v <= start {
+        v
+    } else if v >= len - end {
+        target - (len - v)
+    } else {
+        start + (v - start) * (target - start - end) / (len - start - end)
+    }
+}
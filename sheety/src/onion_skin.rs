@@ -0,0 +1,164 @@
+use image::{Rgba, RgbaImage};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{
+    error::{Error, Result},
+    Sprite, UnorderedSpriteSheet,
+};
+
+/// How to draw the frames around a frame of an animation as ghosts over it, to review the motion, see
+/// [UnorderedSpriteSheet::onion_skin].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OnionSkin {
+    /// The amount of frames before the frame to draw.
+    pub previous: usize,
+    /// The amount of frames after the frame to draw.
+    pub next: usize,
+    /// The colour mixed into the frames before the frame, its alpha being how much of it is mixed in.
+    pub previous_tint: Rgba<u8>,
+    /// The colour mixed into the frames after the frame, its alpha being how much of it is mixed in.
+    pub next_tint: Rgba<u8>,
+    /// The opacity of the closest frames, from `0.0` to `1.0`.
+    pub opacity: f32,
+    /// How much the opacity is multiplied by for each frame further away, e.g. `0.5` halves it every frame.
+    pub falloff: f32,
+    /// Whether the animation loops, so that the last frames come before the first one and the other way around.
+    /// Each other frame is then drawn once at most: short loops draw fewer frames, the closest ones first.
+    pub looping: bool,
+}
+
+impl Default for OnionSkin {
+    fn default() -> Self {
+        Self {
+            previous: 1,
+            next: 1,
+            previous_tint: Rgba([255, 0, 0, 128]),
+            next_tint: Rgba([0, 255, 0, 128]),
+            opacity: 0.5,
+            falloff: 0.5,
+            looping: false,
+        }
+    }
+}
+
+impl UnorderedSpriteSheet {
+    /// Draws the [Sprite] at index `frame` over the tinted [Sprite]s around it, according to `options`. Further
+    /// [Sprite]s are drawn first, so that closer ones end up over them. The result keeps the name, the
+    /// [SpriteSource][crate::SpriteSource], the pivot and the other data of the [Sprite] at index `frame`.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::OutOfBounds] if there is no [Sprite] at index `frame`.
+    pub fn onion_skin(&self, frame: usize, options: &OnionSkin) -> Result<Sprite> {
+        if frame >= self.len() {
+            return Err(Error::OutOfBounds {
+                max: (self.len(), 0),
+                provided: (frame, 0),
+            });
+        }
+
+        Ok(self.onion_skin_unchecked(frame, options))
+    }
+
+    /// Replaces every [Sprite] with its onion-skinned version, see [UnorderedSpriteSheet::onion_skin], e.g. to
+    /// review a whole animation as a sheet. With the `rayon` feature, [Sprite]s are drawn in parallel.
+    pub fn onion_skin_all(&mut self, options: &OnionSkin) {
+        #[cfg(feature = "rayon")]
+        let frames = (0..self.len()).into_par_iter();
+        #[cfg(not(feature = "rayon"))]
+        let frames = 0..self.len();
+
+        let sprites: Vec<Sprite> = frames
+            .map(|frame| self.onion_skin_unchecked(frame, options))
+            .collect();
+
+        *self.inner_mut() = sprites;
+    }
+
+    /// Same as [UnorderedSpriteSheet::onion_skin], but `frame` must be the index of a [Sprite].
+    fn onion_skin_unchecked(&self, frame: usize, options: &OnionSkin) -> Sprite {
+        let sprites = self.inner();
+        let current = &sprites[frame];
+
+        let (width, height) = (self.size().0 as u32, self.size().1 as u32);
+        let mut pixels = RgbaImage::new(width, height);
+        let len = sprites.len() as isize;
+
+        // Looping around would otherwise draw frames several times, or the frame as a ghost of itself.
+        let (previous, next) = match options.looping {
+            true => {
+                let others = sprites.len() - 1;
+                let previous = options
+                    .previous
+                    .min(others.div_ceil(2).max(others.saturating_sub(options.next)));
+
+                (previous, options.next.min(others - previous))
+            }
+            false => (options.previous, options.next),
+        };
+
+        for distance in (1..=previous.max(next)).rev() {
+            let opacity = options.opacity * options.falloff.powi(distance as i32 - 1);
+
+            for (offset, count, tint) in [
+                (-(distance as isize), previous, options.previous_tint),
+                (distance as isize, next, options.next_tint),
+            ] {
+                let index = frame as isize + offset;
+                let index = match options.looping {
+                    true => index.rem_euclid(len),
+                    false => index,
+                };
+
+                if distance > count || !(0..len).contains(&index) {
+                    continue;
+                }
+
+                draw(&mut pixels, &sprites[index as usize], Some(tint), opacity);
+            }
+        }
+
+        draw(&mut pixels, current, None, 1.0);
+
+        current.derive(pixels, |point| point)
+    }
+}
+
+/// Draws `sprite` over `pixels`, mixed with `tint` if there is one, with its alpha multiplied by `opacity`.
+fn draw(pixels: &mut RgbaImage, sprite: &Sprite, tint: Option<Rgba<u8>>, opacity: f32) {
+    let width = pixels.width() as usize;
+    let target = pixels.as_mut();
+
+    for (y, row) in sprite.raw_rows().enumerate() {
+        for (x, source) in row.chunks_exact(4).enumerate() {
+            let start = (y * width + x) * 4;
+            let destination = &mut target[start..start + 4];
+
+            let mut colour = [source[0], source[1], source[2]].map(|c| c as f32);
+
+            if let Some(tint) = tint {
+                let amount = tint[3] as f32 / 255.0;
+
+                for (c, t) in colour.iter_mut().zip(tint.0) {
+                    *c += (t as f32 - *c) * amount;
+                }
+            }
+
+            let alpha = source[3] as f32 / 255.0 * opacity.clamp(0.0, 1.0);
+            let below = destination[3] as f32 / 255.0 * (1.0 - alpha);
+            let total = alpha + below;
+
+            if total <= 0.0 {
+                continue;
+            }
+
+            for (i, c) in colour.iter().enumerate() {
+                destination[i] =
+                    f32::round((c * alpha + destination[i] as f32 * below) / total) as u8;
+            }
+
+            destination[3] = f32::round(total * 255.0) as u8;
+        }
+    }
+}
@@ -0,0 +1,376 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{CollisionMask, Sprite};
+
+type Point = (f32, f32);
+
+/// How far, in pixels, [Outline::simplify] may move edges outwards to drop runs of vertices at once, before
+/// removing the remaining ones one at a time.
+const RUN_TOLERANCE: f32 = 1.0;
+
+/// A polygon around the solid pixels of a [Sprite], e.g. to draw it with a tight mesh rather than a quad, see
+/// [Sprite::trace_outlines].
+///
+/// Its vertices are in pixels from the top left corner of the [Sprite], going clockwise on screen.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Outline {
+    /// The vertices of the polygon.
+    pub vertices: Vec<(f32, f32)>,
+    /// The triangles covering the polygon, as indices in [Outline::vertices]; empty until
+    /// [Outline::triangulate] is called.
+    pub triangles: Vec<[usize; 3]>,
+}
+
+impl Outline {
+    /// Makes an [Outline] from its `vertices`, without triangles.
+    pub fn new(vertices: Vec<(f32, f32)>) -> Self {
+        Self {
+            vertices,
+            triangles: Vec::new(),
+        }
+    }
+
+    /// Gets the area of the polygon, in pixels.
+    pub fn area(&self) -> f32 {
+        let n = self.vertices.len();
+
+        (0..n)
+            .map(|i| cross((0.0, 0.0), self.vertices[i], self.vertices[(i + 1) % n]))
+            .sum::<f32>()
+            / 2.0
+    }
+
+    /// Returns the convex hull of this [Outline], without triangles.
+    pub fn convex_hull(&self) -> Outline {
+        let mut points = self.vertices.clone();
+        points.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        points.dedup();
+
+        if points.len() < 3 {
+            return Outline::new(points);
+        }
+
+        // Andrew's monotone chain, keeping right turns, so that the hull goes clockwise on screen.
+        let mut hull: Vec<Point> = Vec::new();
+
+        for pass in [
+            &points[..],
+            &points.iter().rev().copied().collect::<Vec<_>>()[..],
+        ] {
+            let start = hull.len();
+
+            for point in pass.iter() {
+                while hull.len() >= start + 2
+                    && cross(hull[hull.len() - 2], hull[hull.len() - 1], *point) <= 0.0
+                {
+                    hull.pop();
+                }
+
+                hull.push(*point);
+            }
+
+            hull.pop();
+        }
+
+        Outline::new(hull)
+    }
+
+    /// Fills [Outline::triangles] with triangles covering the polygon, by ear clipping. The polygon should not
+    /// cross itself.
+    pub fn triangulate(&mut self) {
+        let mut remaining: Vec<usize> = (0..self.vertices.len()).collect();
+        self.triangles.clear();
+
+        while remaining.len() > 3 {
+            let n = remaining.len();
+
+            let ear = (0..n).find(|i| {
+                let (a, b, c) = (
+                    remaining[(i + n - 1) % n],
+                    remaining[*i],
+                    remaining[(i + 1) % n],
+                );
+
+                cross(self.vertices[a], self.vertices[b], self.vertices[c]) > 0.0
+                    && !remaining
+                        .iter()
+                        .filter(|j| ![a, b, c].contains(j))
+                        .any(|j| {
+                            in_triangle(self.vertices[*j], [a, b, c].map(|k| self.vertices[k]))
+                        })
+            });
+
+            // Degenerate polygons may have no ear left, their remaining vertices are flat.
+            let Some(i) = ear else {
+                break;
+            };
+
+            self.triangles.push([
+                remaining[(i + n - 1) % n],
+                remaining[i],
+                remaining[(i + 1) % n],
+            ]);
+            remaining.remove(i);
+        }
+
+        if remaining.len() == 3 {
+            self.triangles
+                .push([remaining[0], remaining[1], remaining[2]]);
+        }
+    }
+
+    /// Moves the vertices of this [Outline] with `map`, keeping them clockwise if `map` mirrors them.
+    pub(crate) fn map<F>(&self, map: F) -> Outline
+    where
+        F: Fn(Point) -> Point,
+    {
+        let mut outline = Outline {
+            vertices: self.vertices.iter().map(|vertex| map(*vertex)).collect(),
+            triangles: self.triangles.clone(),
+        };
+
+        if (outline.area() < 0.0) != (self.area() < 0.0) {
+            let n = outline.vertices.len();
+
+            outline.vertices.reverse();
+
+            for triangle in outline.triangles.iter_mut() {
+                *triangle = [
+                    n - 1 - triangle[2],
+                    n - 1 - triangle[1],
+                    n - 1 - triangle[0],
+                ];
+            }
+        }
+
+        outline
+    }
+
+    /// Removes vertices until there are at most `max_vertices`, only ever growing the polygon. Runs of vertices
+    /// close to a straight edge, such as the steps of traced diagonals, are dropped first with [without_runs].
+    /// Concave vertices are then removed, in the order of the area they add, then edges of the remaining convex
+    /// polygon are replaced by the extension of their neighbours.
+    fn simplify(&mut self, max_vertices: usize) {
+        if self.vertices.len() > max_vertices.max(3) {
+            self.vertices = without_runs(std::mem::take(&mut self.vertices));
+        }
+
+        while self.vertices.len() > max_vertices.max(3) {
+            let vertices = &self.vertices;
+            let n = vertices.len();
+            let at = |i: usize| vertices[i % n];
+
+            let concave = (0..n)
+                .filter_map(|i| {
+                    let (a, b, c) = (at(i + n - 1), at(i), at(i + 1));
+                    let turn = cross(a, b, c);
+
+                    let blocked = turn < 0.0
+                        && (0..n)
+                            .filter(|j| ![(i + n - 1) % n, i, (i + 1) % n].contains(j))
+                            .any(|j| in_triangle(at(j), [a, b, c]));
+
+                    (turn <= 0.0 && !blocked).then_some((i, -turn))
+                })
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+
+            if let Some((i, _)) = concave {
+                self.vertices.remove(i);
+                continue;
+            }
+
+            // Concave vertices that can't be removed without crossing another edge are covered by the hull.
+            if (0..n).any(|i| cross(at(i + n - 1), at(i), at(i + 1)) <= 0.0) {
+                self.vertices = self.convex_hull().vertices;
+                continue;
+            }
+
+            let extension = (0..n)
+                .filter_map(|i| {
+                    let (a, b, c, d) = (at(i + n - 1), at(i), at(i + 1), at(i + 2));
+                    let p = intersection(a, b, d, c)?;
+
+                    Some((i, p, cross(b, p, c).abs()))
+                })
+                .min_by(|a, b| a.2.total_cmp(&b.2));
+
+            let Some((i, p, _)) = extension else {
+                break;
+            };
+
+            self.vertices[i] = p;
+            self.vertices.remove((i + 1) % n);
+        }
+    }
+}
+
+impl Sprite {
+    /// Traces the [Outline]s of the solid pixels of this [Sprite], one per group of pixels touching by their
+    /// edges, where pixels are solid if their alpha is greater than `threshold`, like
+    /// [Sprite::collision_mask]. Holes are filled.
+    ///
+    /// Outlines are simplified until they have at most `max_vertices` vertices, without ever leaving pixels out,
+    /// or until they can't lose a vertex without growing without bounds, e.g. a rectangle keeps its 4 vertices.
+    pub fn trace_outlines(&self, threshold: u8, max_vertices: usize) -> Vec<Outline> {
+        trace(&self.collision_mask(threshold))
+            .into_iter()
+            .map(|mut outline| {
+                outline.simplify(max_vertices);
+                outline
+            })
+            .collect()
+    }
+}
+
+/// Traces the boundaries of the solid pixels of `mask` along the edges of the pixels, like marching squares,
+/// keeping outer boundaries only.
+fn trace(mask: &CollisionMask) -> Vec<Outline> {
+    let (width, height) = mask.size();
+    let solid =
+        |x: i64, y: i64| x >= 0 && y >= 0 && mask.get((x as usize, y as usize)).unwrap_or(false);
+
+    // Edges between solid and non-solid pixels, going clockwise on screen around solid pixels, by start corner.
+    let mut edges: HashMap<(i64, i64), Vec<(i64, i64)>> = HashMap::new();
+
+    for y in 0..height as i64 {
+        for x in 0..width as i64 {
+            if !solid(x, y) {
+                continue;
+            }
+
+            for (neighbour, from, to) in [
+                ((x, y - 1), (x, y), (x + 1, y)),
+                ((x + 1, y), (x + 1, y), (x + 1, y + 1)),
+                ((x, y + 1), (x + 1, y + 1), (x, y + 1)),
+                ((x - 1, y), (x, y + 1), (x, y)),
+            ] {
+                if !solid(neighbour.0, neighbour.1) {
+                    edges.entry(from).or_default().push(to);
+                }
+            }
+        }
+    }
+
+    let mut starts: Vec<(i64, i64)> = edges.keys().copied().collect();
+    starts.sort_by_key(|(x, y)| (*y, *x));
+
+    let mut outlines = Vec::new();
+
+    for start in starts {
+        while let Some(first) = edges.get_mut(&start).and_then(Vec::pop) {
+            let mut corners = vec![start];
+            let (mut from, mut to) = (start, first);
+
+            while to != start {
+                let direction = (to.0 - from.0, to.1 - from.1);
+                let outgoing = edges.get_mut(&to).filter(|outgoing| !outgoing.is_empty());
+
+                // Where two solid pixels only touch by a corner, turn right so that they stay apart.
+                let Some(outgoing) = outgoing else {
+                    break;
+                };
+                let right = (to.0 - direction.1, to.1 + direction.0);
+                let next = match outgoing.iter().position(|corner| *corner == right) {
+                    Some(i) => outgoing.swap_remove(i),
+                    None => outgoing.swap_remove(0),
+                };
+
+                corners.push(to);
+                (from, to) = (to, next);
+            }
+
+            let outline = Outline::new(without_collinear(corners));
+
+            if outline.area() > 0.0 {
+                outlines.push(outline);
+            }
+        }
+    }
+
+    outlines
+}
+
+/// Converts `corners` to points, removing those in the middle of straight lines.
+fn without_collinear(corners: Vec<(i64, i64)>) -> Vec<Point> {
+    let n = corners.len();
+
+    (0..n)
+        .filter(|i| {
+            let (a, b, c) = (corners[(i + n - 1) % n], corners[*i], corners[(i + 1) % n]);
+            (b.0 - a.0) * (c.1 - b.1) != (b.1 - a.1) * (c.0 - b.0)
+        })
+        .map(|i| (corners[i].0 as f32, corners[i].1 as f32))
+        .collect()
+}
+
+/// Replaces runs of `vertices` with straight edges, like the Douglas–Peucker algorithm, but only where no vertex
+/// of the run is outside the edge nor further than [RUN_TOLERANCE] inside it, so that the polygon only grows.
+fn without_runs(vertices: Vec<Point>) -> Vec<Point> {
+    let n = vertices.len();
+
+    if n <= 4 {
+        return vertices;
+    }
+
+    let at = |i: usize| vertices[i % n];
+    let mut kept = vec![false; n];
+    let mut runs = vec![(0, n / 2), (n / 2, n)];
+    (kept[0], kept[n / 2]) = (true, true);
+
+    while let Some((start, end)) = runs.pop() {
+        let (a, b) = (at(start), at(end));
+        let length = (b.0 - a.0).hypot(b.1 - a.1);
+        // How far each vertex of the run is outside the edge from `a` to `b`, negative inside.
+        let outside = |i: &usize| cross(a, at(*i), b) / length;
+
+        let split = (start + 1..end)
+            .filter(|i| outside(i) > 0.0)
+            .max_by(|i, j| outside(i).total_cmp(&outside(j)))
+            .or_else(|| {
+                (start + 1..end)
+                    .filter(|i| outside(i) < -RUN_TOLERANCE)
+                    .min_by(|i, j| outside(i).total_cmp(&outside(j)))
+            });
+
+        if let Some(i) = split {
+            kept[i] = true;
+            runs.extend([(start, i), (i, end)]);
+        }
+    }
+
+    (0..n).filter(|i| kept[*i]).map(at).collect()
+}
+
+/// Gets the cross product of `b - a` and `c - b`, positive if `a`, `b`, `c` turn clockwise on screen.
+fn cross(a: Point, b: Point, c: Point) -> f32 {
+    (b.0 - a.0) * (c.1 - b.1) - (b.1 - a.1) * (c.0 - b.0)
+}
+
+/// Returns `true` if `p` is in `triangle`, or on its edges.
+fn in_triangle(p: Point, triangle: [Point; 3]) -> bool {
+    let [a, b, c] = triangle;
+    let sides = [cross(a, b, p), cross(b, c, p), cross(c, a, p)];
+
+    sides.iter().all(|side| *side >= 0.0) || sides.iter().all(|side| *side <= 0.0)
+}
+
+/// Gets the intersection of the ray from `a` through `b` and the ray from `d` through `c`, if it is past `b` and
+/// `c`.
+fn intersection(a: Point, b: Point, d: Point, c: Point) -> Option<Point> {
+    let r = (b.0 - a.0, b.1 - a.1);
+    let s = (c.0 - d.0, c.1 - d.1);
+    let denominator = r.0 * s.1 - r.1 * s.0;
+
+    if denominator.abs() < f32::EPSILON {
+        return None;
+    }
+
+    let t = ((c.0 - b.0) * s.1 - (c.1 - b.1) * s.0) / denominator;
+    let u = ((c.0 - b.0) * r.1 - (c.1 - b.1) * r.0) / denominator;
+
+    (t > 0.0 && u > 0.0).then_some((b.0 + t * r.0, b.1 + t * r.1))
+}
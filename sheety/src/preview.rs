@@ -0,0 +1,257 @@
+use image::{GenericImageView, Rgba, RgbaImage};
+
+use crate::{
+    error::{Error, Result},
+    sprite_cell::SpriteCell,
+    SheetMetadata, SpriteSheet,
+};
+
+/// How to draw the preview of a [SpriteSheet], see [SpriteSheet::preview].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreviewOptions {
+    /// The size of each pixel of the sheet in the preview, e.g. `4` to make pixel art readable.
+    pub scale: u32,
+    /// The colour of the lines drawn between cells, or [None] for no lines.
+    pub grid: Option<Rgba<u8>>,
+    /// The two colours of the checkerboard drawn behind transparent pixels, or [None] to keep them transparent.
+    pub checkerboard: Option<[Rgba<u8>; 2]>,
+    /// The size of the squares of the checkerboard, in pixels of the preview.
+    pub checker_size: u32,
+    /// Whether to write the index of each frame, and its name if it has one, in the top left corner of its cell.
+    pub labels: bool,
+    /// The size of each pixel of the labels, whose characters are 3x5 pixels.
+    pub label_scale: u32,
+}
+
+impl Default for PreviewOptions {
+    fn default() -> Self {
+        Self {
+            scale: 1,
+            grid: Some(Rgba([255, 0, 255, 255])),
+            checkerboard: Some([Rgba([204, 204, 204, 255]), Rgba([255, 255, 255, 255])]),
+            checker_size: 8,
+            labels: true,
+            label_scale: 2,
+        }
+    }
+}
+
+impl SpriteSheet {
+    /// Draws a preview of this [SpriteSheet] according to `options`, e.g. to know which frame has which index. The
+    /// preview is `options.scale` times bigger, plus a line of 1 pixel around each cell if there is a grid.
+    ///
+    /// Frames are numbered like [SpriteSheet::into_unordered], or in the order of `metadata` if it is given, in
+    /// which case fully transparent frames are numbered as well.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::InvalidMetadata] if `metadata` doesn't have the size and cell size of this
+    ///   [SpriteSheet].
+    pub fn preview(
+        &self,
+        options: &PreviewOptions,
+        metadata: Option<&SheetMetadata>,
+    ) -> Result<RgbaImage> {
+        let (size, cell_size) = (self.size(), self.cell_size());
+
+        let frames: Vec<usize> = match metadata {
+            Some(metadata) if metadata.size != size || metadata.cell_size != cell_size => {
+                return Err(Error::InvalidMetadata(format!(
+                    "the sheet is {size:?} cells of {cell_size:?} pixels, not {:?} cells of {:?} pixels",
+                    metadata.size, metadata.cell_size,
+                )))
+            }
+            Some(metadata) => metadata.frames.clone(),
+            None => self
+                .cells()
+                .enumerate()
+                .filter(|(_, cell)| matches!(cell, SpriteCell::Sprite(_)))
+                .map(|(i, _)| i)
+                .collect(),
+        };
+
+        let scale = options.scale.max(1);
+        let line = options.grid.is_some() as u32;
+        let cell = (cell_size.0 as u32 * scale, cell_size.1 as u32 * scale);
+        let stride = (cell.0 + line, cell.1 + line);
+
+        let mut preview = RgbaImage::new(
+            size.0 as u32 * stride.0 + line,
+            size.1 as u32 * stride.1 + line,
+        );
+
+        for (i, sprite_cell) in self.cells().enumerate() {
+            let origin = (
+                line + (i % size.0) as u32 * stride.0,
+                line + (i / size.0) as u32 * stride.1,
+            );
+            let view = match sprite_cell {
+                SpriteCell::Sprite(sprite) => Some(sprite.view()),
+                SpriteCell::Empty => None,
+            };
+
+            for y in 0..cell.1 {
+                for x in 0..cell.0 {
+                    let pixel = view.as_ref().map_or(Rgba([0, 0, 0, 0]), |view| {
+                        view.get_pixel(x / scale, y / scale)
+                    });
+
+                    let pixel = match options.checkerboard {
+                        Some(colours) => {
+                            let square = options.checker_size.max(1);
+                            blend(pixel, colours[((x / square + y / square) % 2) as usize])
+                        }
+                        None => pixel,
+                    };
+
+                    preview.put_pixel(origin.0 + x, origin.1 + y, pixel);
+                }
+            }
+        }
+
+        if options.labels {
+            for (frame, i) in frames.iter().enumerate() {
+                let label = match self.get_cell((i % size.0, i / size.0)) {
+                    Ok(SpriteCell::Sprite(sprite)) => match sprite.name() {
+                        Some(name) => format!("{frame} {name}"),
+                        None => frame.to_string(),
+                    },
+                    _ => match metadata
+                        .and_then(|metadata| metadata.info.get(&frame)?.name.as_ref())
+                    {
+                        Some(name) => format!("{frame} {name}"),
+                        None => frame.to_string(),
+                    },
+                };
+
+                let origin = (
+                    line + (i % size.0) as u32 * stride.0,
+                    line + (i / size.0) as u32 * stride.1,
+                );
+                draw_label(
+                    &mut preview,
+                    &label,
+                    origin,
+                    cell,
+                    options.label_scale.max(1),
+                );
+            }
+        }
+
+        if let Some(colour) = options.grid {
+            let (width, height) = preview.dimensions();
+
+            for x in (0..width).step_by(stride.0 as usize) {
+                for y in 0..height {
+                    preview.put_pixel(x, y, colour);
+                }
+            }
+
+            for y in (0..height).step_by(stride.1 as usize) {
+                for x in 0..width {
+                    preview.put_pixel(x, y, colour);
+                }
+            }
+        }
+
+        Ok(preview)
+    }
+}
+
+/// Draws `pixel` over the opaque `background`.
+fn blend(pixel: Rgba<u8>, background: Rgba<u8>) -> Rgba<u8> {
+    let alpha = pixel[3] as u32;
+    let mix =
+        |i: usize| ((pixel[i] as u32 * alpha + background[i] as u32 * (255 - alpha)) / 255) as u8;
+
+    Rgba([mix(0), mix(1), mix(2), 255])
+}
+
+/// Writes `label` in white on black at the top left corner of the cell of size `cell` at `origin`, cutting what
+/// doesn't fit in the cell.
+fn draw_label(
+    preview: &mut RgbaImage,
+    label: &str,
+    origin: (u32, u32),
+    cell: (u32, u32),
+    scale: u32,
+) {
+    // Characters are 3 pixels wide with 1 pixel between them, and the label has a margin of 1 pixel.
+    let width = (label.chars().count() as u32 * 4 + 1) * scale;
+    let height = 7 * scale;
+
+    for y in 0..height.min(cell.1) {
+        for x in 0..width.min(cell.0) {
+            let (column, row) = (x / scale, y / scale);
+
+            let lit = column % 4 != 0
+                && (1..6).contains(&row)
+                && label
+                    .chars()
+                    .nth((column / 4) as usize)
+                    .is_some_and(|c| glyph(c)[(row - 1) as usize] & (0b1000 >> (column % 4)) != 0);
+
+            let colour = if lit { 255 } else { 0 };
+            preview.put_pixel(
+                origin.0 + x,
+                origin.1 + y,
+                Rgba([colour, colour, colour, 255]),
+            );
+        }
+    }
+}
+
+/// Gets the lines of the 3x5 glyph of `c`, from top to bottom, the left column being the third bit. Lowercase
+/// letters are drawn as uppercase ones, and unknown characters as `?`.
+fn glyph(c: char) -> [u8; 5] {
+    match c.to_ascii_uppercase() {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        ' ' => [0b000, 0b000, 0b000, 0b000, 0b000],
+        '_' => [0b000, 0b000, 0b000, 0b000, 0b111],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        '=' => [0b000, 0b111, 0b000, 0b111, 0b000],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+        '#' => [0b101, 0b111, 0b101, 0b111, 0b101],
+        '(' => [0b010, 0b100, 0b100, 0b100, 0b010],
+        ')' => [0b010, 0b001, 0b001, 0b001, 0b010],
+        _ => [0b111, 0b001, 0b010, 0b000, 0b010],
+    }
+}
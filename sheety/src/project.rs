@@ -0,0 +1,209 @@
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+    sync::Arc,
+};
+
+use crate::{
+    error::{Error, Result},
+    metadata::{cell_count, SheetMetadata},
+    sprite_cell::SpriteCell,
+    Sprite, SpriteSheet,
+};
+
+/// The first bytes of a `.sheety` project file.
+const PROJECT_MAGIC: &[u8; 8] = b"\x89SHEETY\n";
+
+/// Holds the [SheetMetadata] of the sheet, in its text format.
+const CHUNK_METADATA: &[u8; 4] = b"META";
+/// Holds the pixels of the sheet, as a PNG image.
+const CHUNK_PIXELS: &[u8; 4] = b"PIXL";
+/// Marks the end of the project.
+const CHUNK_END: &[u8; 4] = b"IEND";
+
+impl SpriteSheet {
+    /// Writes this [SpriteSheet] and its `metadata` as a `.sheety` project into `writer`. Unlike a PNG image,
+    /// a project keeps the difference between empty cells and fully transparent frames, and the pixels of fully
+    /// transparent frames.
+    ///
+    /// A project starts with the bytes `\x89SHEETY\n`, followed by chunks made of a 4 bytes ASCII id, a little
+    /// endian `u64` length and their data: `META` holds the [SheetMetadata] in its text format, `PIXL` the pixels
+    /// as a PNG image, and `IEND` ends the project. Readers skip chunks they don't know.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::InvalidMetadata] if `metadata` doesn't have the size and cell size of this
+    ///   [SpriteSheet].
+    /// - Will return [Error::PngEncodingError] if the pixels could not be encoded, e.g. if the sheet is empty.
+    /// - Will return [Error::IoError] if writing to `writer` fails.
+    pub fn write_project<W>(&self, mut writer: W, metadata: &SheetMetadata) -> Result<()>
+    where
+        W: Write,
+    {
+        if self.size() != metadata.size || self.cell_size() != metadata.cell_size {
+            return Err(Error::InvalidMetadata(format!(
+                "the sheet is {:?} cells of {:?} pixels, not {:?} cells of {:?} pixels",
+                self.size(),
+                self.cell_size(),
+                metadata.size,
+                metadata.cell_size,
+            )));
+        }
+
+        let mut pixels = Vec::new();
+        self.write_png(&mut pixels, None)?;
+
+        writer.write_all(PROJECT_MAGIC)?;
+        write_chunk(&mut writer, CHUNK_METADATA, metadata.to_string().as_bytes())?;
+        write_chunk(&mut writer, CHUNK_PIXELS, &pixels)?;
+        write_chunk(&mut writer, CHUNK_END, &[])?;
+
+        Ok(())
+    }
+
+    /// Saves this [SpriteSheet] and its `metadata` as a `.sheety` project to the disk, whatever the file extension.
+    /// See [SpriteSheet::write_project].
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::IoError] if the file could not be created or written to.
+    /// - Will return the errors of [SpriteSheet::write_project].
+    pub fn save_project<P>(&self, path: P, metadata: &SheetMetadata) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_project(&mut writer, metadata)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Reads a [SpriteSheet] and its [SheetMetadata] from a `.sheety` project read from `reader`, see
+    /// [SpriteSheet::write_project]. Cells holding frames are never empty, even if they are fully transparent;
+    /// other cells are empty if they are fully transparent. Frames get their names and [SpriteSource][crate::SpriteSource]s back.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::InvalidProject] if the data isn't a valid project.
+    /// - Will return [Error::InvalidMetadata] if the [SheetMetadata] of the project could not be parsed, or doesn't
+    ///   match its pixels.
+    /// - Will return [Error::ImageError] if the pixels could not be decoded.
+    /// - Will return [Error::IoError] if reading from `reader` fails.
+    pub fn read_project<R>(mut reader: R) -> Result<(Self, SheetMetadata)>
+    where
+        R: Read,
+    {
+        let mut magic = [0; PROJECT_MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+
+        if &magic != PROJECT_MAGIC {
+            return Err(Error::InvalidProject("not a sheety project".to_owned()));
+        }
+
+        let (mut metadata, mut pixels) = (None, None);
+
+        loop {
+            let (id, data) = read_chunk(&mut reader)?;
+
+            match &id {
+                CHUNK_METADATA => {
+                    let text = String::from_utf8(data).map_err(|_| {
+                        Error::InvalidMetadata("the metadata isn't valid UTF-8".to_owned())
+                    })?;
+                    metadata = Some(text.parse::<SheetMetadata>()?);
+                }
+                CHUNK_PIXELS => pixels = Some(data),
+                CHUNK_END => break,
+                _ => {}
+            }
+        }
+
+        let missing = |chunk: &[u8; 4]| {
+            Error::InvalidProject(format!(
+                "missing `{}` chunk",
+                String::from_utf8_lossy(chunk)
+            ))
+        };
+
+        let metadata = metadata.ok_or_else(|| missing(CHUNK_METADATA))?;
+        let pixels = pixels.ok_or_else(|| missing(CHUNK_PIXELS))?;
+
+        let image = Arc::new(image::load_from_memory(&pixels)?.into_rgba8());
+        let (size, cell_size) = (metadata.size, metadata.cell_size);
+
+        metadata.check_image_size((image.width() as usize, image.height() as usize))?;
+
+        let mut sheet = Self::new(size, cell_size);
+        let mut frames = vec![false; cell_count(size)?];
+
+        for i in metadata.frames.iter() {
+            frames[*i] = true;
+        }
+
+        for ((i, cell), frame) in sheet.cells_mut().enumerate().zip(frames) {
+            let sprite = Sprite::view_of(
+                Arc::clone(&image),
+                ((i % size.0) * cell_size.0, (i / size.0) * cell_size.1),
+                cell_size,
+            )?;
+
+            if frame || !sprite.is_empty() {
+                *cell = SpriteCell::Sprite(sprite);
+            }
+        }
+
+        metadata.apply_to(&mut sheet);
+
+        Ok((sheet, metadata))
+    }
+
+    /// Loads a [SpriteSheet] and its [SheetMetadata] from a `.sheety` project on the disk.
+    /// See [SpriteSheet::read_project].
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::IoError] if the file could not be opened.
+    /// - Will return the errors of [SpriteSheet::read_project].
+    pub fn load_project<P>(path: P) -> Result<(Self, SheetMetadata)>
+    where
+        P: AsRef<Path>,
+    {
+        Self::read_project(BufReader::new(File::open(path)?))
+    }
+}
+
+fn write_chunk<W>(writer: &mut W, id: &[u8; 4], data: &[u8]) -> Result<()>
+where
+    W: Write,
+{
+    writer.write_all(id)?;
+    writer.write_all(&(data.len() as u64).to_le_bytes())?;
+    writer.write_all(data)?;
+
+    Ok(())
+}
+
+fn read_chunk<R>(reader: &mut R) -> Result<([u8; 4], Vec<u8>)>
+where
+    R: Read,
+{
+    let mut id = [0; 4];
+    let mut len = [0; 8];
+    reader.read_exact(&mut id)?;
+    reader.read_exact(&mut len)?;
+
+    let len = u64::from_le_bytes(len);
+    let mut data = Vec::new();
+    reader.take(len).read_to_end(&mut data)?;
+
+    if data.len() as u64 != len {
+        return Err(Error::InvalidProject(format!(
+            "chunk `{}` is truncated",
+            String::from_utf8_lossy(&id)
+        )));
+    }
+
+    Ok((id, data))
+}
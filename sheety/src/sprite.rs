@@ -1,21 +1,65 @@
-use std::path::Path;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
-use image::{DynamicImage, RgbaImage};
+use image::{imageops, DynamicImage, RgbaImage, SubImage};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 use crate::{
     error::{Error, Result},
     utils::IVec2,
+    Anchor, CollisionBox, Fit, NineSlice, Outline,
 };
 
 /// Wrapper around [image::RgbaImage] that allows use with [SpriteSheet][crate::SpriteSheet]
 /// and [UnorederedSpriteSheet][crate::UnorderedSpriteSheet]
+///
+/// A [Sprite] either has its own pixels, or is a view over a rectangle of a shared source image, like the
+/// [Sprite]s of a [SpriteSheet][crate::SpriteSheet] loaded from an image. Views don't copy any pixel until they
+/// are mutated with [Sprite::image_mut] or turned into an image with [Sprite::into_image]; they keep the whole
+/// source image alive though.
+///
+/// In both cases, pixels are shared between clones, and only copied when one of the clones is mutated, so
+/// cloning a [Sprite] is cheap.
+///
+/// A [Sprite] can also have a name and a [SpriteSource], which are kept through the operations of the sheets,
+/// and a pivot, [CollisionBox]es, [Outline]s and [NineSlice] borders, which follow its pixels when it is flipped
+/// or fitted.
 #[derive(Debug, Clone)]
 pub struct Sprite {
-    image: RgbaImage,
+    storage: Storage,
+    name: Option<Arc<str>>,
+    source: Option<Arc<SpriteSource>>,
+    pivot: Option<(f32, f32)>,
+    boxes: Arc<[CollisionBox]>,
+    outlines: Arc<[Outline]>,
+    nine_slice: Option<NineSlice>,
+}
+
+/// Where a [Sprite] comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpriteSource {
+    /// The image file the [Sprite] was loaded from, if it was loaded from a file.
+    pub path: Option<PathBuf>,
+    /// The coordinates of the cell the [Sprite] was sliced from, `(0, 0)` if it is a whole image.
+    pub cell: IVec2,
+}
+
+#[derive(Debug, Clone)]
+enum Storage {
+    Owned(Arc<RgbaImage>),
+    View {
+        source: Arc<RgbaImage>,
+        position: (u32, u32),
+        size: (u32, u32),
+    },
 }
 
 impl Sprite {
-    /// Attempts to load a [Sprite] by openning an image.
+    /// Attempts to load a [Sprite] by openning an image. Its [SpriteSource] is the image file.
     ///
     /// # Errors
     ///
@@ -24,35 +68,370 @@ impl Sprite {
     where
         P: AsRef<Path>,
     {
-        Ok(Self::from(image::open(path).map_err(Error::ImageError)?))
+        let sprite = Self::from(image::open(path.as_ref()).map_err(Error::ImageError)?);
+
+        Ok(sprite.with_source(SpriteSource {
+            path: Some(path.as_ref().to_path_buf()),
+            cell: (0, 0),
+        }))
+    }
+
+    /// Makes a [Sprite] that is a view over the rectangle of `source` at `position` of size `size`, in pixels.
+    /// No pixel is copied.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::OutOfBounds] if the rectangle doesn't fit in `source`.
+    pub fn view_of(source: Arc<RgbaImage>, position: IVec2, size: IVec2) -> Result<Sprite> {
+        let max = (source.width() as usize, source.height() as usize);
+
+        if position.0 + size.0 > max.0 || position.1 + size.1 > max.1 {
+            return Err(Error::OutOfBounds {
+                max,
+                provided: (position.0 + size.0, position.1 + size.1),
+            });
+        }
+
+        Ok(Self::with_storage(Storage::View {
+            source,
+            position: (position.0 as u32, position.1 as u32),
+            size: (size.0 as u32, size.1 as u32),
+        }))
+    }
+
+    fn with_storage(storage: Storage) -> Self {
+        Self {
+            storage,
+            name: None,
+            source: None,
+            pivot: None,
+            boxes: Arc::new([]),
+            outlines: Arc::new([]),
+            nine_slice: None,
+        }
+    }
+
+    /// Gets the name of this [Sprite], if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Sets or removes the name of this [Sprite].
+    pub fn set_name(&mut self, name: Option<&str>) {
+        self.name = name.map(Arc::from);
+    }
+
+    /// Returns this [Sprite], named `name`.
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.set_name(Some(name));
+        self
+    }
+
+    /// Gets where this [Sprite] comes from, if known.
+    pub fn source(&self) -> Option<&SpriteSource> {
+        self.source.as_deref()
+    }
+
+    /// Sets or removes where this [Sprite] comes from.
+    pub fn set_source(&mut self, source: Option<SpriteSource>) {
+        self.source = source.map(Arc::new);
+    }
+
+    /// Returns this [Sprite], coming from `source`.
+    pub fn with_source(mut self, source: SpriteSource) -> Self {
+        self.set_source(Some(source));
+        self
+    }
+
+    /// Gets the pivot of this [Sprite], if it has one, in pixels from its top left corner, e.g. the feet of a
+    /// character.
+    pub fn pivot(&self) -> Option<(f32, f32)> {
+        self.pivot
+    }
+
+    /// Sets or removes the pivot of this [Sprite], in pixels from its top left corner.
+    pub fn set_pivot(&mut self, pivot: Option<(f32, f32)>) {
+        self.pivot = pivot;
+    }
+
+    /// Returns this [Sprite], with its pivot at `pivot`, in pixels from its top left corner.
+    pub fn with_pivot(mut self, pivot: (f32, f32)) -> Self {
+        self.set_pivot(Some(pivot));
+        self
+    }
+
+    /// Sets the pivot of this [Sprite] at `anchor`, e.g. [Anchor::Bottom] for the middle of its bottom edge.
+    pub fn set_pivot_anchor(&mut self, anchor: Anchor) {
+        let (x, y) = anchor.relative();
+        self.pivot = Some((x * self.size().0 as f32, y * self.size().1 as f32));
+    }
+
+    /// Gets the [CollisionBox]es of this [Sprite].
+    pub fn boxes(&self) -> &[CollisionBox] {
+        &self.boxes
+    }
+
+    /// Replaces the [CollisionBox]es of this [Sprite].
+    pub fn set_boxes(&mut self, boxes: Vec<CollisionBox>) {
+        self.boxes = boxes.into();
+    }
+
+    /// Returns this [Sprite], with `collision_box` added to its [CollisionBox]es.
+    pub fn with_box(mut self, collision_box: CollisionBox) -> Self {
+        let mut boxes = self.boxes.to_vec();
+        boxes.push(collision_box);
+        self.set_boxes(boxes);
+        self
+    }
+
+    /// Gets the [Outline]s of this [Sprite], see [Sprite::trace_outlines].
+    pub fn outlines(&self) -> &[Outline] {
+        &self.outlines
+    }
+
+    /// Replaces the [Outline]s of this [Sprite].
+    pub fn set_outlines(&mut self, outlines: Vec<Outline>) {
+        self.outlines = outlines.into();
+    }
+
+    /// Gets the [NineSlice] borders of this [Sprite], if it has some, see [Sprite::nine_slice_to].
+    pub fn nine_slice(&self) -> Option<NineSlice> {
+        self.nine_slice
+    }
+
+    /// Sets or removes the [NineSlice] borders of this [Sprite].
+    pub fn set_nine_slice(&mut self, nine_slice: Option<NineSlice>) {
+        self.nine_slice = nine_slice;
+    }
+
+    /// Sets the [NineSlice] borders of this [Sprite].
+    pub fn with_nine_slice(mut self, nine_slice: NineSlice) -> Self {
+        self.set_nine_slice(Some(nine_slice));
+        self
+    }
+
+    /// Gives `pixels` the name and the [SpriteSource] of this [Sprite], and its pivot, [CollisionBox]es,
+    /// [Outline]s and [NineSlice] borders moved with `map`, which maps points of this [Sprite] to points of
+    /// `pixels`. Boxes and borders are clipped to `pixels`, outlines aren't.
+    pub(crate) fn derive<F>(&self, pixels: RgbaImage, map: F) -> Sprite
+    where
+        F: Fn((f32, f32)) -> (f32, f32),
+    {
+        let size = (pixels.width() as usize, pixels.height() as usize);
+
+        Self {
+            name: self.name.clone(),
+            source: self.source.clone(),
+            pivot: self.pivot.map(&map),
+            boxes: self
+                .boxes
+                .iter()
+                .filter_map(|collision_box| collision_box.map(size, &map))
+                .collect(),
+            outlines: self
+                .outlines
+                .iter()
+                .map(|outline| outline.map(&map))
+                .collect(),
+            nine_slice: self
+                .nine_slice
+                .map(|nine_slice| nine_slice.map(self.size(), size, &map)),
+            ..pixels.into()
+        }
     }
 
     /// Gets the size of the underlying [image::RgbaImage].
     pub fn size(&self) -> IVec2 {
-        (self.image.width() as usize, self.image.height() as usize)
+        let (_, _, size) = self.parts();
+        (size.0 as usize, size.1 as usize)
+    }
+
+    /// Returns `true` if this [Sprite] is a view over a source image, rather than owning its pixels.
+    pub fn is_view(&self) -> bool {
+        matches!(self.storage, Storage::View { .. })
+    }
+
+    /// Returns a view of the pixels of this [Sprite], without copying them.
+    pub fn view(&self) -> SubImage<&RgbaImage> {
+        let (image, position, size) = self.parts();
+        imageops::crop_imm(image, position.0, position.1, size.0, size.1)
+    }
+
+    /// Returns `true` if this [Sprite] and `other` share the same pixels, which happens when one is a clone
+    /// of the other that hasn't been mutated since, or when they are views over the same source image.
+    pub fn shares_pixels_with(&self, other: &Sprite) -> bool {
+        Arc::ptr_eq(self.pixels(), other.pixels())
+    }
+
+    /// Returns a mutable reference to the pixels of this [Sprite]. If its pixels are shared with other
+    /// [Sprite]s, or if it is a view, they are copied first.
+    pub fn image_mut(&mut self) -> &mut RgbaImage {
+        if let Storage::View { .. } = self.storage {
+            self.storage = Storage::Owned(Arc::new(self.view().to_image()));
+        }
+
+        match &mut self.storage {
+            Storage::Owned(image) => Arc::make_mut(image),
+            Storage::View { .. } => unreachable!("views have just been turned into owned images"),
+        }
     }
 
     /// Consumes this [Sprite] and returns its underlying [image::RgbaImage].
+    /// Its pixels are only copied if they are shared with other [Sprite]s, or if it is a view over a part of
+    /// a source image.
     pub fn into_image(self) -> RgbaImage {
-        self.image
+        let (image, position, size) = self.parts();
+
+        if position != (0, 0) || size != image.dimensions() {
+            return self.view().to_image();
+        }
+
+        let source = match self.storage {
+            Storage::Owned(image) => image,
+            Storage::View { source, .. } => source,
+        };
+
+        Arc::try_unwrap(source).unwrap_or_else(|source| (*source).clone())
+    }
+
+    fn pixels(&self) -> &Arc<RgbaImage> {
+        match &self.storage {
+            Storage::Owned(image) => image,
+            Storage::View { source, .. } => source,
+        }
+    }
+
+    /// Gets the image holding the pixels of this [Sprite], and the position and size of the [Sprite] in it.
+    fn parts(&self) -> (&RgbaImage, (u32, u32), (u32, u32)) {
+        match &self.storage {
+            Storage::Owned(image) => (image, (0, 0), image.dimensions()),
+            Storage::View {
+                source,
+                position,
+                size,
+            } => (source, *position, *size),
+        }
+    }
+
+    /// Returns the raw RGBA bytes of the line of pixels `y` of this [Sprite].
+    fn raw_row(&self, y: usize) -> &[u8] {
+        let (image, position, size) = self.parts();
+        let start = ((position.1 as usize + y) * image.width() as usize + position.0 as usize) * 4;
+
+        &image.as_raw()[start..start + size.0 as usize * 4]
+    }
+
+    /// Returns an iterator of the raw RGBA bytes of each line of pixels of this [Sprite], from top to bottom.
+    pub(crate) fn raw_rows(&self) -> impl Iterator<Item = &[u8]> {
+        (0..self.size().1).map(|y| self.raw_row(y))
+    }
+
+    /// Same as [Sprite::raw_rows], but lines are scanned in parallel with the `rayon` feature.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn par_raw_rows(&self) -> impl IndexedParallelIterator<Item = &[u8]> {
+        (0..self.size().1).into_par_iter().map(|y| self.raw_row(y))
+    }
+
+    /// Same as [Sprite::raw_rows], but lines are scanned in parallel with the `rayon` feature.
+    #[cfg(not(feature = "rayon"))]
+    pub(crate) fn par_raw_rows(&self) -> impl Iterator<Item = &[u8]> {
+        self.raw_rows()
+    }
+
+    /// Copies the pixels of this [Sprite] into `target`, the raw RGBA bytes of an image `width` pixels wide, with
+    /// its top left corner at `position`, without blending.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [Sprite] doesn't fit in `target` at `position`.
+    pub(crate) fn write_to(&self, target: &mut [u8], width: usize, position: IVec2) {
+        let stride = width * 4;
+
+        for (i, row) in self.raw_rows().enumerate() {
+            let start = (position.1 + i) * stride + position.0 * 4;
+            target[start..start + row.len()].copy_from_slice(row);
+        }
+    }
+
+    /// Returns a copy of this [Sprite], flipped horizontally. The copy keeps the name and the [SpriteSource], and
+    /// its pivot, [CollisionBox]es, [Outline]s and [NineSlice] borders are flipped as well.
+    pub fn flip_horizontal(&self) -> Sprite {
+        let width = self.size().0 as f32;
+
+        self.derive(imageops::flip_horizontal(&*self.view()), |(x, y)| {
+            (width - x, y)
+        })
+    }
+
+    /// Returns a copy of this [Sprite], fitted to `size` according to `fit`.
+    /// Pixels outside the original [Sprite] are left fully transparent. The copy keeps the name and the
+    /// [SpriteSource], and its pivot, [CollisionBox]es, [Outline]s and [NineSlice] borders stay on the same pixels.
+    pub fn fit(&self, size: IVec2, fit: Fit) -> Sprite {
+        let view = self.view();
+        let (width, height) = (self.size().0 as u32, self.size().1 as u32);
+
+        let (anchor, scaled) = match fit {
+            Fit::Anchor(anchor) => (anchor, None),
+            Fit::Scale(anchor) => {
+                let factor = f32::min(size.0 as f32 / width as f32, size.1 as f32 / height as f32);
+
+                (
+                    anchor,
+                    Some(imageops::resize(
+                        &*view,
+                        u32::max(1, f32::round(width as f32 * factor) as u32),
+                        u32::max(1, f32::round(height as f32 * factor) as u32),
+                        imageops::FilterType::Nearest,
+                    )),
+                )
+            }
+        };
+
+        let (x, y) = anchor.relative();
+        let mut fitted = RgbaImage::new(size.0 as u32, size.1 as u32);
+
+        let (new_width, new_height) = scaled
+            .as_ref()
+            .map_or((width, height), |scaled| scaled.dimensions());
+        let offset = (
+            f32::round((size.0 as f32 - new_width as f32) * x) as i64,
+            f32::round((size.1 as f32 - new_height as f32) * y) as i64,
+        );
+
+        match &scaled {
+            Some(scaled) => imageops::replace(&mut fitted, scaled, offset.0, offset.1),
+            None => imageops::replace(&mut fitted, &*view, offset.0, offset.1),
+        }
+
+        let factor = (
+            new_width as f32 / width as f32,
+            new_height as f32 / height as f32,
+        );
+
+        self.derive(fitted, |(x, y)| {
+            (
+                x * factor.0 + offset.0 as f32,
+                y * factor.1 + offset.1 as f32,
+            )
+        })
     }
 
     /// Returns `true` if the [Sprite] only has fully transparent pixels.
+    /// With the `rayon` feature, lines of pixels are checked in parallel.
     pub fn is_empty(&self) -> bool {
-        self.image.pixels().all(|px| px.0[3] == 0)
+        self.par_raw_rows()
+            .all(|row| row.chunks_exact(4).all(|px| px[3] == 0))
     }
 }
 
 impl From<DynamicImage> for Sprite {
     fn from(val: DynamicImage) -> Self {
-        Self {
-            image: val.into_rgba8(),
-        }
+        Self::with_storage(Storage::Owned(Arc::new(val.into_rgba8())))
     }
 }
 
 impl From<RgbaImage> for Sprite {
     fn from(val: RgbaImage) -> Self {
-        Self { image: val }
+        Self::with_storage(Storage::Owned(Arc::new(val)))
     }
 }
@@ -1,26 +1,37 @@
-use std::{path::Path, vec};
+use std::{iter::FusedIterator, path::Path, slice, sync::Arc, vec};
 
-use image::{GenericImage, GenericImageView, RgbaImage};
+use image::RgbaImage;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 use crate::{
     error::{Error, Result},
     sprite_cell::SpriteCell,
     unordered_sprite_sheet::UnorderedSpriteSheet,
     utils::IVec2,
-    Distribution, Sprite,
+    AnimationTag, Distribution, Sprite, SpriteSource,
 };
 
 const EXPECT_MSG_OUTOFBOUNDS: &str =
-    "coords have already been checked to be inbounds and should be inbounds by this point; \
-	if this panic occurs then the SpriteSheet.size is not in sync with the size of the inner \
-	SpriteSheet.cells vector";
+    "coords have already been checked to be inbounds and should be inbounds by this point";
 
 const EXPECT_MSG_SHEET_FULL: &str =
     "Distribution::get_min_size should always return a size that fits";
 
+/// What to do with the pixels left over on the right and at the bottom of an image that isn't made of whole cells,
+/// when slicing it into a [SpriteSheet], e.g. with [SpriteSheet::try_from_image_cell_size].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Remainder {
+    /// Drops them.
+    #[default]
+    Allow,
+    /// Rejects the image with [Error::IndivisibleSheet], since its cell size is likely wrong.
+    Reject,
+}
+
 /// An ordered sprite sheet. Contains a 2 dimensions array of [SpriteCell]s.
 pub struct SpriteSheet {
-    cells: Vec<Vec<SpriteCell>>, // Vector of lines, each line is a vector of cells
+    cells: Vec<SpriteCell>, // All cells, line after line; always holds `size.0 * size.1` cells
     size: IVec2,
     cell_size: IVec2,
 }
@@ -33,22 +44,14 @@ impl SpriteSheet {
     /// # Examples
     ///
     /// ```
+    /// # use sheety::SpriteSheet;
     /// let sheet = SpriteSheet::new((5, 5), (128, 128));
     ///
     /// assert!(sheet.cells().all(|cell| cell.is_empty()));
     /// ```
     pub fn new(size: IVec2, cell_size: IVec2) -> SpriteSheet {
         SpriteSheet {
-            cells: {
-                let mut temp_vec = Vec::new(); // vector of lines
-                temp_vec.reserve_exact(size.1);
-
-                for _ in 0..size.1 {
-                    temp_vec.push(vec![SpriteCell::Empty; size.0]);
-                }
-
-                temp_vec
-            },
+            cells: vec![SpriteCell::Empty; size.0 * size.1],
             size,
             cell_size,
         }
@@ -60,42 +63,30 @@ impl SpriteSheet {
         self.size
     }
 
+    /// Gets the size, in pixels, of the [Sprite]s of the [SpriteSheet].
+    #[inline(always)]
+    pub fn cell_size(&self) -> IVec2 {
+        self.cell_size
+    }
+
     /// Returns an immutable reference to the cell at cell coordonates `coords`.
     ///
     /// # Errors
     ///
-    /// - Will return [Error::OutOfRange] if the specified `coords` are out of bounds.
+    /// - Will return [Error::OutOfBounds] if the specified `coords` are out of bounds.
     pub fn get_cell(&self, coords: IVec2) -> Result<&SpriteCell> {
-        self.cells
-            .get(coords.1)
-            .ok_or(Error::OutOfBounds {
-                max: self.size,
-                provided: coords,
-            })?
-            .get(coords.0)
-            .ok_or(Error::OutOfBounds {
-                max: self.size,
-                provided: coords,
-            })
+        let index = self.index(coords)?;
+        Ok(&self.cells[index])
     }
 
     /// Returns a mutable reference to the cell at cell coordonates `coords`.
     ///
     /// # Errors
     ///
-    /// - Will return [Error::OutOfRange] if the specified `coords` are out of bounds.
+    /// - Will return [Error::OutOfBounds] if the specified `coords` are out of bounds.
     pub fn get_cell_mut(&mut self, coords: IVec2) -> Result<&mut SpriteCell> {
-        self.cells
-            .get_mut(coords.1)
-            .ok_or(Error::OutOfBounds {
-                max: self.size,
-                provided: coords,
-            })?
-            .get_mut(coords.0)
-            .ok_or(Error::OutOfBounds {
-                max: self.size,
-                provided: coords,
-            })
+        let index = self.index(coords)?;
+        Ok(&mut self.cells[index])
     }
 
     /// Sets the value of the cell at coordonates `coords` to the specified `cell`, returning the previous value
@@ -103,14 +94,10 @@ impl SpriteSheet {
     ///
     /// # Errors
     ///
-    /// - Will return [Error::OutOfRange] if the specified `coords` are out of bounds.
+    /// - Will return [Error::OutOfBounds] if the specified `coords` are out of bounds.
+    /// - Will return [Error::MismatchedSpriteSize] if the [Sprite] of `cell` isn't of the size of the cells.
     pub fn set_cell(&mut self, coords: IVec2, cell: SpriteCell) -> Result<SpriteCell> {
-        if coords.0 > self.size.0 || coords.1 > self.size.1 {
-            return Err(Error::OutOfBounds {
-                max: self.size,
-                provided: coords,
-            });
-        }
+        let index = self.index(coords)?;
 
         if let Some(size) = cell.size() {
             if size != self.cell_size {
@@ -121,26 +108,87 @@ impl SpriteSheet {
             }
         }
 
-        Ok(std::mem::replace(
-            self.cells
-                .get_mut(coords.1)
-                .expect(EXPECT_MSG_OUTOFBOUNDS)
-                .get_mut(coords.0)
-                .expect(EXPECT_MSG_OUTOFBOUNDS),
-            cell,
-        ))
+        Ok(std::mem::replace(&mut self.cells[index], cell))
+    }
+
+    /// Gets the index of the cell at coordonates `coords` in the inner vector of cells.
+    fn index(&self, coords: IVec2) -> Result<usize> {
+        if coords.0 >= self.size.0 || coords.1 >= self.size.1 {
+            return Err(Error::OutOfBounds {
+                max: self.size,
+                provided: coords,
+            });
+        }
+
+        Ok(coords.1 * self.size.0 + coords.0)
+    }
+
+    /// Gets the coordonates of the first cell, line after line, holding a [Sprite] named `name`.
+    pub fn coords_of(&self, name: &str) -> Option<IVec2> {
+        self.cells
+            .iter()
+            .position(
+                |cell| matches!(cell, SpriteCell::Sprite(sprite) if sprite.name() == Some(name)),
+            )
+            .map(|i| (i % self.size.0, i / self.size.0))
+    }
+
+    /// Returns an immutable reference to the first [Sprite], line after line, named `name`.
+    pub fn get_by_name(&self, name: &str) -> Option<&Sprite> {
+        self.cells.iter().find_map(|cell| match cell {
+            SpriteCell::Sprite(sprite) if sprite.name() == Some(name) => Some(sprite),
+            _ => None,
+        })
+    }
+
+    /// Sets the path of the [SpriteSource]s of the [Sprite]s that don't have one.
+    pub(crate) fn set_source_path(&mut self, path: &Path) {
+        for cell in self.cells.iter_mut() {
+            if let SpriteCell::Sprite(sprite) = cell {
+                if let Some(source) = sprite.source().filter(|source| source.path.is_none()) {
+                    let cell = source.cell;
+
+                    sprite.set_source(Some(SpriteSource {
+                        path: Some(path.to_path_buf()),
+                        cell,
+                    }));
+                }
+            }
+        }
     }
 
     /// Returns an immutable iterator of all cells contained in the [SpriteSheet].
     /// Cells are iterated from top left, to max width, and then to max height.
-    pub fn cells(&self) -> IterCells {
-        IterCells::new(self)
+    pub fn cells(&self) -> IterCells<'_> {
+        IterCells(self.cells.iter())
     }
 
     /// Returns a mutable iterator of all cells contained in the [SpriteSheet].
     /// Cells are iterated from top left, to max width, and then to max height.
-    pub fn cells_mut(&mut self) -> IterCellsMut {
-        IterCellsMut::new(self)
+    pub fn cells_mut(&mut self) -> IterCellsMut<'_> {
+        IterCellsMut(self.cells.iter_mut())
+    }
+
+    /// Returns an iterator of the lines of the [SpriteSheet], from top to bottom. Each line is a slice of its
+    /// cells, from left to right.
+    pub fn rows(&self) -> Rows<'_> {
+        Rows(self.cells.chunks_exact(usize::max(self.size.0, 1)))
+    }
+
+    /// Returns a mutable iterator of the lines of the [SpriteSheet], from top to bottom. Each line is a slice of
+    /// its cells, from left to right.
+    pub fn rows_mut(&mut self) -> RowsMut<'_> {
+        RowsMut(self.cells.chunks_exact_mut(usize::max(self.size.0, 1)))
+    }
+
+    /// Returns an iterator of the columns of the [SpriteSheet], from left to right. Each column is an iterator
+    /// of its cells, from top to bottom.
+    pub fn columns(&self) -> Columns<'_> {
+        Columns {
+            cells: &self.cells,
+            width: self.size.0,
+            range: 0..if self.size.1 == 0 { 0 } else { self.size.0 },
+        }
     }
 
     /// Consumes this [SpriteSheet] and makes an [UnorderedSpriteSheet] containing all the *non-empty* cells
@@ -169,7 +217,7 @@ impl SpriteSheet {
     /// # Errors
     ///
     /// - Will return [Error::SheetFull] if not all sprites were able to fit in the [SpriteSheet]. The ones that
-    /// did fit though, will still be pushed into the [SpriteSheet].
+    ///   did fit though, will still be pushed into the [SpriteSheet].
     pub fn push_sprites(&mut self, sprites: UnorderedSpriteSheet) -> Result<()> {
         for (fitted, sprite) in sprites.into_iter().enumerate() {
             self.push_sprite(sprite).map_err(|_| Error::SheetFull {
@@ -193,20 +241,89 @@ impl SpriteSheet {
     /// # Errors
     ///
     /// - Will return [Error::MismatchedSpriteSize] if all the [UnorderedSpriteSheet] don't
-    /// all have the same [Sprite] size.
+    ///   all have the same [Sprite] size.
     pub fn concat<I>(sprites: I, distribution: Distribution) -> Result<Self>
     where
         I: Iterator<Item = UnorderedSpriteSheet>,
     {
         let list: Vec<UnorderedSpriteSheet> = sprites.collect();
+        let size = Self::common_size(&list)?;
+        let len = list.iter().map(|unordered| unordered.len()).sum();
+
+        let mut sheet = Self::new(distribution.get_min_size(len), size);
 
-        if list.is_empty() {
-            return Err(Error::EmptyIterator);
+        for unordered in list {
+            sheet.push_sprites(unordered).expect(EXPECT_MSG_SHEET_FULL);
         }
 
-        let size = list[0].size();
+        Ok(sheet)
+    }
 
-        let mut len = 0;
+    /// Zips the [UnorderedSpriteSheet]s given in `sprites` frame by frame, so that each of them becomes a line
+    /// of the [SpriteSheet]. Lines of shorter [UnorderedSpriteSheet]s are padded with [SpriteCell::Empty] cells.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::EmptyIterator] if `sprites` is empty.
+    /// - Will return [Error::MismatchedSpriteSize] if all the [UnorderedSpriteSheet] don't
+    ///   all have the same [Sprite] size.
+    pub fn zip_lines<I>(sprites: I) -> Result<Self>
+    where
+        I: Iterator<Item = UnorderedSpriteSheet>,
+    {
+        Self::zip(sprites.collect(), false)
+    }
+
+    /// Zips the [UnorderedSpriteSheet]s given in `sprites` frame by frame, so that each of them becomes a
+    /// column of the [SpriteSheet]. Columns of shorter [UnorderedSpriteSheet]s are padded with [SpriteCell::Empty]
+    /// cells. Iterating over the cells of the result interleaves the [UnorderedSpriteSheet]s: `a0, b0, a1, b1, ...`
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::EmptyIterator] if `sprites` is empty.
+    /// - Will return [Error::MismatchedSpriteSize] if all the [UnorderedSpriteSheet] don't
+    ///   all have the same [Sprite] size.
+    pub fn zip_columns<I>(sprites: I) -> Result<Self>
+    where
+        I: Iterator<Item = UnorderedSpriteSheet>,
+    {
+        Self::zip(sprites.collect(), true)
+    }
+
+    /// Zips the [UnorderedSpriteSheet]s of `list`, each of them becoming a column of the [SpriteSheet] if `columns`
+    /// is `true`, or a line otherwise.
+    fn zip(list: Vec<UnorderedSpriteSheet>, columns: bool) -> Result<Self> {
+        let size = Self::common_size(&list)?;
+        let len = list
+            .iter()
+            .map(|unordered| unordered.len())
+            .max()
+            .unwrap_or(0);
+
+        let mut sheet = match columns {
+            true => Self::new((list.len(), len), size),
+            false => Self::new((len, list.len()), size),
+        };
+
+        for (i, unordered) in list.into_iter().enumerate() {
+            for (frame, sprite) in unordered.into_iter().enumerate() {
+                let coords = match columns {
+                    true => (i, frame),
+                    false => (frame, i),
+                };
+
+                sheet
+                    .set_cell(coords, SpriteCell::Sprite(sprite))
+                    .expect(EXPECT_MSG_OUTOFBOUNDS);
+            }
+        }
+
+        Ok(sheet)
+    }
+
+    /// Returns the [Sprite] size shared by all the [UnorderedSpriteSheet]s of `list`.
+    fn common_size(list: &[UnorderedSpriteSheet]) -> Result<IVec2> {
+        let size = list.first().ok_or(Error::EmptyIterator)?.size();
 
         for unordered in list.iter() {
             if unordered.size() != size {
@@ -215,21 +332,18 @@ impl SpriteSheet {
                     provided: unordered.size(),
                 });
             }
-
-            len += unordered.len();
-        }
-
-        let mut sheet = Self::new(distribution.get_min_size(len), size);
-
-        for unordered in list {
-            sheet.push_sprites(unordered).expect(EXPECT_MSG_SHEET_FULL);
         }
 
-        Ok(sheet)
+        Ok(size)
     }
 
     /// Makes a [SpriteSheet] from a full [Sprite] that contains all the cells.
-    /// Divides the sheet according to the given number of divisions.
+    /// Divides the sheet according to the given number of divisions. Pixels left over on the right and at the
+    /// bottom are dropped; see [SpriteSheet::try_from_image_div] to reject them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisions` has a 0 in it.
     pub fn from_image_div(sprite: Sprite, divisions: IVec2) -> Self {
         let cell_size = (sprite.size().0 / divisions.0, sprite.size().1 / divisions.1);
 
@@ -237,241 +351,408 @@ impl SpriteSheet {
     }
 
     /// Makes a [SpriteSheet] from a full [Sprite] that contains all the cells.
-    /// Divides the sheet according to the cell size.
+    /// Divides the sheet according to the cell size. Pixels left over on the right and at the bottom are dropped;
+    /// see [SpriteSheet::try_from_image_cell_size] to reject them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cell_size` has a 0 in it.
     pub fn from_image_cell_size(sprite: Sprite, cell_size: IVec2) -> Self {
         let divisions = (sprite.size().0 / cell_size.0, sprite.size().1 / cell_size.1);
 
         Self::from_image(sprite, divisions, cell_size)
     }
 
-    fn from_image(sprite: Sprite, divisions: IVec2, cell_size: IVec2) -> Self {
-        let image = sprite.into_image();
+    /// Same as [SpriteSheet::from_image_div], but checks the divisions, and what to do with the pixels left over
+    /// according to `remainder`.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::ZeroCellSize] if `divisions` has a 0 in it, or if there are more divisions than
+    ///   pixels.
+    /// - Will return [Error::IndivisibleSheet] if `remainder` is [Remainder::Reject] and `sprite` isn't made of
+    ///   whole cells.
+    pub fn try_from_image_div(
+        sprite: Sprite,
+        divisions: IVec2,
+        remainder: Remainder,
+    ) -> Result<Self> {
+        let (divisions, cell_size) = slice_div(sprite.size(), divisions, remainder)?;
+
+        Ok(Self::from_image(sprite, divisions, cell_size))
+    }
+
+    /// Same as [SpriteSheet::from_image_cell_size], but checks the cell size, and what to do with the pixels left
+    /// over according to `remainder`.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::ZeroCellSize] if `cell_size` has a 0 in it.
+    /// - Will return [Error::IndivisibleSheet] if `remainder` is [Remainder::Reject] and `sprite` isn't made of
+    ///   whole cells.
+    pub fn try_from_image_cell_size(
+        sprite: Sprite,
+        cell_size: IVec2,
+        remainder: Remainder,
+    ) -> Result<Self> {
+        let (divisions, cell_size) = slice_cell_size(sprite.size(), cell_size, remainder)?;
+
+        Ok(Self::from_image(sprite, divisions, cell_size))
+    }
+
+    /// Every cell of the [SpriteSheet] is a view over `sprite`, so that no pixel is copied.
+    /// With the `rayon` feature, cells are sliced in parallel.
+    pub(crate) fn from_image(sprite: Sprite, divisions: IVec2, cell_size: IVec2) -> Self {
+        let path = sprite.source().and_then(|source| source.path.clone());
+        let image = Arc::new(sprite.into_image());
 
         let mut sheet = Self::new(divisions, cell_size);
 
-        for x in 0..divisions.0 {
-            for y in 0..divisions.1 {
-                let sub_sprite: Sprite = image
-                    .view(
-                        (x * cell_size.0) as u32,
-                        (y * cell_size.1) as u32,
-                        cell_size.0 as u32,
-                        cell_size.1 as u32,
-                    )
-                    .to_image()
-                    .into();
+        #[cfg(feature = "rayon")]
+        let cells = sheet.cells.par_iter_mut();
+        #[cfg(not(feature = "rayon"))]
+        let cells = sheet.cells.iter_mut();
 
-                sheet
-                    .set_cell(
-                        (x, y),
-                        if sub_sprite.is_empty() {
-                            SpriteCell::Empty
-                        } else {
-                            SpriteCell::Sprite(sub_sprite)
-                        },
-                    )
-                    .expect(EXPECT_MSG_OUTOFBOUNDS);
+        cells.enumerate().for_each(|(i, cell)| {
+            let (x, y) = (i % divisions.0, i / divisions.0);
+
+            let sub_sprite = Sprite::view_of(
+                Arc::clone(&image),
+                (x * cell_size.0, y * cell_size.1),
+                cell_size,
+            )
+            .expect(EXPECT_MSG_OUTOFBOUNDS)
+            .with_source(SpriteSource {
+                path: path.clone(),
+                cell: (x, y),
+            });
+
+            if !sub_sprite.is_empty() {
+                *cell = SpriteCell::Sprite(sub_sprite);
             }
-        }
+        });
 
         sheet
     }
 
     /// Consumes this [SpriteSheet], returning an [image::RgbaImage].
-    pub fn into_image(mut self) -> RgbaImage {
-        let mut final_image = RgbaImage::new(
-            (self.cell_size.0 * self.size.0) as u32,
-            (self.cell_size.1 * self.size.1) as u32,
-        );
-
-        for x in 0..self.size.0 {
-            for y in 0..self.size.1 {
-                match std::mem::replace(
-                    self.cells[y].get_mut(x).expect(EXPECT_MSG_OUTOFBOUNDS),
-                    SpriteCell::Empty,
-                ) {
-                    SpriteCell::Sprite(sprite) => final_image
-                        .copy_from(
-                            &sprite.into_image(),
-                            (x * self.cell_size.0) as u32,
-                            (y * self.cell_size.1) as u32,
-                        )
-                        .expect("image should have already been checked to be of the right size at insertion time"),
-                    SpriteCell::Empty => (),
-                }
-            }
+    /// With the `rayon` feature, lines of cells are composited in parallel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a [Sprite] put in the [SpriteSheet] through [SpriteSheet::cells_mut] isn't of the size of the
+    /// cells.
+    pub fn into_image(self) -> RgbaImage {
+        let width = self.cell_size.0 * self.size.0;
+        let mut final_image = RgbaImage::new(width as u32, (self.cell_size.1 * self.size.1) as u32);
+        let band_len = width * 4 * self.cell_size.1;
+
+        if band_len == 0 {
+            return final_image;
         }
 
+        #[cfg(feature = "rayon")]
+        let bands = final_image
+            .par_chunks_exact_mut(band_len)
+            .zip(self.cells.par_chunks_exact(self.size.0));
+        #[cfg(not(feature = "rayon"))]
+        let bands = final_image
+            .chunks_exact_mut(band_len)
+            .zip(self.cells.chunks_exact(self.size.0));
+
+        bands.for_each(|(band, row)| self.write_row(row, band));
+
         final_image
     }
 
+    /// Copies the [Sprite]s of `row`, a line of cells of this [SpriteSheet], into `band`, the raw RGBA bytes of
+    /// that line in the full image.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a [Sprite] isn't of the size of the cells.
+    pub(crate) fn write_row(&self, row: &[SpriteCell], band: &mut [u8]) {
+        for (x, cell) in row.iter().enumerate() {
+            if let SpriteCell::Sprite(sprite) = cell {
+                assert_eq!(
+                    sprite.size(),
+                    self.cell_size,
+                    "sprites should all be of the size of the cells"
+                );
+
+                sprite.write_to(
+                    band,
+                    self.cell_size.0 * self.size.0,
+                    (x * self.cell_size.0, 0),
+                );
+            }
+        }
+    }
+
     /// Loads a [SpriteSheet] from an image on the disk that contains all the cells.
-    /// Divides the sheet according to the given number of divisions.
+    /// Divides the sheet according to the given number of divisions. Pixels left over on the right and at the
+    /// bottom are dropped; see [SpriteSheet::try_from_image_div] to reject them.
     ///
     /// # Errors
     ///
     /// - Will return [Error::ImageError] if the underlying call to [image::open] returns an error.
+    /// - Will return [Error::ZeroCellSize] if `divisions` has a 0 in it, or if there are more divisions than
+    ///   pixels.
     pub fn load_div<P>(path: P, divisions: IVec2) -> Result<Self>
     where
         P: AsRef<Path>,
     {
-        Ok(Self::from_image_div(Sprite::load(path)?, divisions))
+        Self::try_from_image_div(Sprite::load(path)?, divisions, Remainder::Allow)
     }
 
     /// Loads a [SpriteSheet] from an image on the disk that contains all the cells.
-    /// Divides the sheet according to the cell size.
+    /// Divides the sheet according to the cell size. Pixels left over on the right and at the bottom are dropped;
+    /// see [SpriteSheet::try_from_image_cell_size] to reject them.
     ///
     /// # Errors
     ///
     /// - Will return [Error::ImageError] if the underlying call to [image::open] returns an error.
+    /// - Will return [Error::ZeroCellSize] if `cell_size` has a 0 in it.
     pub fn load_cell_size<P>(path: P, cell_size: IVec2) -> Result<Self>
     where
         P: AsRef<Path>,
     {
-        Ok(Self::from_image_cell_size(Sprite::load(path)?, cell_size))
+        Self::try_from_image_cell_size(Sprite::load(path)?, cell_size, Remainder::Allow)
     }
 
     /// Consumes and saves this [SpriteSheet] as an image to the disk.
-    /// Uses [image::RgbaImage::save], so the format will be guessed by the file extension.
+    /// PNG images are streamed one line of cells at a time with [SpriteSheet::save_png], and embed the
+    /// [SheetMetadata][crate::SheetMetadata] of the sheet so that [SpriteSheet::load_with_metadata] can load it
+    /// back on its own; use [SpriteSheet::save_png] to leave it out, or [SpriteSheet::save_with_tags] to add
+    /// animation tags. Files with the `sheety` extension are saved as projects with [SpriteSheet::save_project].
+    /// Other formats use [image::RgbaImage::save], so the format will be guessed by the file extension.
     ///
     /// # Errors
     ///
-    /// - Will return [Error::ImageError] if the underlying call to [image::open] returns an error.
+    /// - Will return [Error::ImageError] if the underlying call to [image::RgbaImage::save] returns an error.
+    /// - Will return [Error::IoError] or [Error::PngEncodingError] if streaming a PNG image or saving a project
+    ///   fails.
     pub fn save<P>(self, path: P) -> Result<()>
     where
         P: AsRef<Path>,
     {
-        self.into_image().save(path).map_err(Error::ImageError)
+        self.save_with_tags(path, &[])
     }
-}
 
-impl IntoIterator for SpriteSheet {
-    type Item = SpriteCell;
+    /// Same as [SpriteSheet::save], but PNG images and projects also keep `tags`, whose frames are indices of the
+    /// non-empty cells of this [SpriteSheet], line after line, like [SpriteSheet::metadata]. Other formats can't
+    /// hold them.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::OutOfBounds] if a tag spans frames that don't exist.
+    /// - Will return [Error::InvalidMetadata] if the name of a tag spans several lines.
+    /// - Will return the errors of [SpriteSheet::save].
+    pub fn save_with_tags<P>(self, path: P, tags: &[AnimationTag]) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let extension = path.as_ref().extension().unwrap_or_default();
 
-    type IntoIter = IntoIterCells;
+        if !extension.eq_ignore_ascii_case("png") && !extension.eq_ignore_ascii_case("sheety") {
+            return self.into_image().save(path).map_err(Error::ImageError);
+        }
 
-    fn into_iter(self) -> Self::IntoIter {
-        Self::IntoIter::new(self)
+        let metadata = tags.iter().try_fold(self.metadata(), |metadata, tag| {
+            metadata.with_tag(tag.name.clone(), tag.frames.clone())
+        })?;
+
+        if extension.eq_ignore_ascii_case("png") {
+            self.save_png(path, Some(&metadata))
+        } else {
+            self.save_project(path, &metadata)
+        }
     }
 }
 
-// IntoIter
+/// Gets the divisions and the cell size of a sheet sliced from an image of `image_size` pixels into `divisions`
+/// cells, checking what is left over according to `remainder`.
+pub(crate) fn slice_div(
+    image_size: IVec2,
+    divisions: IVec2,
+    remainder: Remainder,
+) -> Result<(IVec2, IVec2)> {
+    if divisions.0 == 0 || divisions.1 == 0 {
+        return Err(Error::ZeroCellSize);
+    }
 
-pub struct IntoIterCells {
-    sheet_iter: vec::IntoIter<Vec<SpriteCell>>,
-    line_iter: Option<Vec<SpriteCell>>,
+    let cell_size = (image_size.0 / divisions.0, image_size.1 / divisions.1);
+    check_slicing(image_size, divisions, cell_size, remainder)
 }
 
-impl IntoIterCells {
-    fn new(sheet: SpriteSheet) -> Self {
-        Self {
-            sheet_iter: sheet.cells.into_iter(),
-            line_iter: None,
-        }
+/// Gets the divisions and the cell size of a sheet sliced from an image of `image_size` pixels into cells of
+/// `cell_size` pixels, checking what is left over according to `remainder`.
+pub(crate) fn slice_cell_size(
+    image_size: IVec2,
+    cell_size: IVec2,
+    remainder: Remainder,
+) -> Result<(IVec2, IVec2)> {
+    if cell_size.0 == 0 || cell_size.1 == 0 {
+        return Err(Error::ZeroCellSize);
+    }
+
+    let divisions = (image_size.0 / cell_size.0, image_size.1 / cell_size.1);
+    check_slicing(image_size, divisions, cell_size, remainder)
+}
+
+fn check_slicing(
+    image_size: IVec2,
+    divisions: IVec2,
+    cell_size: IVec2,
+    remainder: Remainder,
+) -> Result<(IVec2, IVec2)> {
+    if cell_size.0 == 0 || cell_size.1 == 0 {
+        return Err(Error::ZeroCellSize);
+    }
+
+    if remainder == Remainder::Reject
+        && (divisions.0 * cell_size.0, divisions.1 * cell_size.1) != image_size
+    {
+        return Err(Error::IndivisibleSheet {
+            image_size,
+            cell_size,
+        });
     }
+
+    Ok((divisions, cell_size))
 }
 
-impl Iterator for IntoIterCells {
+impl IntoIterator for SpriteSheet {
     type Item = SpriteCell;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        // take the next line (if none return none)
-        // take the next cell on the line (if none return none)
-
-        if match self.line_iter {
-            Some(ref it) if it.is_empty() => true, // is it empty
-            None => true,                          // or is it None
-            _ => false,
-        } {
-            self.line_iter = self.sheet_iter.next();
-        }
+    type IntoIter = IntoIterCells;
 
-        self.line_iter.as_mut().map(|vec| vec.remove(0))
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterCells(self.cells.into_iter())
     }
 }
 
-// Iter
+impl<'a> IntoIterator for &'a SpriteSheet {
+    type Item = &'a SpriteCell;
+
+    type IntoIter = IterCells<'a>;
 
-pub struct IterCells<'a> {
-    sheet: &'a SpriteSheet,
-    next_index: usize,
-    current_line: usize,
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells()
+    }
 }
 
-impl<'a> IterCells<'a> {
-    fn new(sheet: &'a SpriteSheet) -> Self {
-        Self {
-            sheet,
-            next_index: 0,
-            current_line: 0,
-        }
+impl<'a> IntoIterator for &'a mut SpriteSheet {
+    type Item = &'a mut SpriteCell;
+
+    type IntoIter = IterCellsMut<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells_mut()
     }
 }
 
-impl<'a> Iterator for IterCells<'a> {
-    type Item = &'a SpriteCell;
+/// Implements [Iterator], [DoubleEndedIterator], [ExactSizeIterator] and [FusedIterator] for a wrapper around
+/// another iterator, by forwarding to it.
+macro_rules! forward_iterator {
+    ($name:ident $(<$lt:lifetime>)?, $item:ty) => {
+        impl$(<$lt>)? Iterator for $name$(<$lt>)? {
+            type Item = $item;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.next_index >= self.sheet.size.0 {
-            self.current_line += 1;
-            self.next_index = 0;
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                self.0.next()
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.0.size_hint()
+            }
 
-            if self.current_line >= self.sheet.size.1 {
-                return None;
+            #[inline]
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                self.0.nth(n)
             }
         }
 
-        let next = self
-            .sheet
-            .cells
-            .get(self.current_line)
-            .expect(EXPECT_MSG_OUTOFBOUNDS)
-            .get(self.next_index)
-            .expect(EXPECT_MSG_OUTOFBOUNDS);
+        impl$(<$lt>)? DoubleEndedIterator for $name$(<$lt>)? {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                self.0.next_back()
+            }
+        }
 
-        self.next_index += 1;
-        Some(next)
-    }
+        impl$(<$lt>)? ExactSizeIterator for $name$(<$lt>)? {}
+
+        impl$(<$lt>)? FusedIterator for $name$(<$lt>)? {}
+    };
 }
 
-pub struct IterCellsMut<'a> {
-    sheet: &'a mut SpriteSheet,
-    next_index: usize,
-    current_line: usize,
+/// An owning iterator of the cells of a [SpriteSheet], see [SpriteSheet::into_iter].
+#[derive(Debug)]
+pub struct IntoIterCells(vec::IntoIter<SpriteCell>);
+
+forward_iterator!(IntoIterCells, SpriteCell);
+
+/// An immutable iterator of the cells of a [SpriteSheet], see [SpriteSheet::cells].
+#[derive(Debug, Clone)]
+pub struct IterCells<'a>(slice::Iter<'a, SpriteCell>);
+
+forward_iterator!(IterCells<'a>, &'a SpriteCell);
+
+/// A mutable iterator of the cells of a [SpriteSheet], see [SpriteSheet::cells_mut].
+#[derive(Debug)]
+pub struct IterCellsMut<'a>(slice::IterMut<'a, SpriteCell>);
+
+forward_iterator!(IterCellsMut<'a>, &'a mut SpriteCell);
+
+/// An immutable iterator of the lines of a [SpriteSheet], see [SpriteSheet::rows].
+#[derive(Debug, Clone)]
+pub struct Rows<'a>(slice::ChunksExact<'a, SpriteCell>);
+
+forward_iterator!(Rows<'a>, &'a [SpriteCell]);
+
+/// A mutable iterator of the lines of a [SpriteSheet], see [SpriteSheet::rows_mut].
+#[derive(Debug)]
+pub struct RowsMut<'a>(slice::ChunksExactMut<'a, SpriteCell>);
+
+forward_iterator!(RowsMut<'a>, &'a mut [SpriteCell]);
+
+/// An iterator of the cells of a single column of a [SpriteSheet], from top to bottom.
+pub type Column<'a> = std::iter::StepBy<slice::Iter<'a, SpriteCell>>;
+
+/// An immutable iterator of the columns of a [SpriteSheet], see [SpriteSheet::columns].
+#[derive(Debug, Clone)]
+pub struct Columns<'a> {
+    cells: &'a [SpriteCell],
+    width: usize,
+    range: std::ops::Range<usize>,
 }
 
-impl<'a> IterCellsMut<'a> {
-    fn new(sheet: &'a mut SpriteSheet) -> Self {
-        Self {
-            sheet,
-            next_index: 0,
-            current_line: 0,
-        }
+impl<'a> Columns<'a> {
+    fn column(&self, x: usize) -> Column<'a> {
+        self.cells[x..].iter().step_by(self.width)
     }
 }
 
-impl<'a> Iterator for IterCellsMut<'a> {
-    type Item = &'a mut SpriteCell;
+impl<'a> Iterator for Columns<'a> {
+    type Item = Column<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.next_index >= self.sheet.size.0 {
-            self.current_line += 1;
-            self.next_index = 0;
-
-            if self.current_line >= self.sheet.size.1 {
-                return None;
-            }
-        }
+        self.range.next().map(|x| self.column(x))
+    }
 
-        let next = self
-            .sheet
-            .cells
-            .get_mut(self.current_line)
-            .expect(EXPECT_MSG_OUTOFBOUNDS)
-            .get_mut(self.next_index)
-            .expect(EXPECT_MSG_OUTOFBOUNDS);
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
 
-        self.next_index += 1;
-        Some(unsafe { std::mem::transmute(next) })
+impl DoubleEndedIterator for Columns<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(|x| self.column(x))
     }
 }
+
+impl ExactSizeIterator for Columns<'_> {}
+
+impl FusedIterator for Columns<'_> {}
@@ -0,0 +1,394 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use image::RgbaImage;
+
+use crate::{
+    error::{Error, Result},
+    metadata::{SheetMetadata, METADATA_KEYWORD},
+    sprite::{Sprite, SpriteSource},
+    sprite_cell::SpriteCell,
+    sprite_sheet::{slice_cell_size, slice_div, Remainder},
+    utils::IVec2,
+    SpriteSheet,
+};
+
+impl SpriteSheet {
+    /// Encodes this [SpriteSheet] as a PNG image into `writer`, one line of cells at a time, so that the
+    /// full image is never held in memory: only a band of the height of a cell is. If given, `metadata` is
+    /// embedded in a text chunk, so that [SpriteSheet::read_png_with_metadata] can read the sheet back on its own.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::PngEncodingError] if the image could not be encoded, e.g. if it is empty.
+    /// - Will return [Error::IoError] if writing to `writer` fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a [Sprite] put in the [SpriteSheet] through [SpriteSheet::cells_mut] isn't of the size of the
+    /// cells.
+    pub fn write_png<W>(&self, writer: W, metadata: Option<&SheetMetadata>) -> Result<()>
+    where
+        W: Write,
+    {
+        let cell_size = self.cell_size();
+        let width = cell_size.0 * self.size().0;
+        let height = cell_size.1 * self.size().1;
+
+        let mut encoder = png::Encoder::new(writer, width as u32, height as u32);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        if let Some(metadata) = metadata {
+            encoder.add_itxt_chunk(METADATA_KEYWORD.to_owned(), metadata.to_string())?;
+        }
+
+        let mut writer = encoder.write_header()?;
+        let mut stream = writer.stream_writer()?;
+        let mut band = RgbaImage::new(width as u32, cell_size.1 as u32);
+
+        for row in self.rows() {
+            band.fill(0);
+
+            self.write_row(row, &mut band);
+            stream.write_all(&band)?;
+        }
+
+        stream.finish()?;
+        writer.finish()?;
+
+        Ok(())
+    }
+
+    /// Saves this [SpriteSheet] as a PNG image to the disk, whatever the file extension, one line of cells at
+    /// a time, optionally embedding `metadata`. See [SpriteSheet::write_png].
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::IoError] if the file could not be created or written to.
+    /// - Will return [Error::PngEncodingError] if the image could not be encoded, e.g. if it is empty.
+    pub fn save_png<P>(&self, path: P, metadata: Option<&SheetMetadata>) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_png(&mut writer, metadata)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Decodes a [SpriteSheet] from a PNG image read from `reader`, one line of cells at a time.
+    /// Divides the sheet according to the given number of divisions. Pixels left over on the right and at the
+    /// bottom are dropped, unless `remainder` is [Remainder::Reject].
+    ///
+    /// Unlike [SpriteSheet::from_image_div], only a band of the height of a cell is decoded at once, and
+    /// each cell owns its pixels, so that the full image is never held in memory. Interlaced images can't be
+    /// decoded band by band though, so they are decoded at once.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::PngDecodingError] if the image could not be decoded.
+    /// - Will return [Error::ZeroCellSize] if `divisions` has a 0 in it, or if there are more divisions than
+    ///   pixels.
+    /// - Will return [Error::IndivisibleSheet] if `remainder` is [Remainder::Reject] and the image isn't made of
+    ///   whole cells.
+    pub fn read_png_div<R>(reader: R, divisions: IVec2, remainder: Remainder) -> Result<Self>
+    where
+        R: Read,
+    {
+        Self::decode_png(reader, |size, _| slice_div(size, divisions, remainder))
+    }
+
+    /// Decodes a [SpriteSheet] from a PNG image read from `reader`, one line of cells at a time.
+    /// Divides the sheet according to the cell size. See [SpriteSheet::read_png_div].
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::PngDecodingError] if the image could not be decoded.
+    /// - Will return [Error::ZeroCellSize] if `cell_size` has a 0 in it.
+    /// - Will return [Error::IndivisibleSheet] if `remainder` is [Remainder::Reject] and the image isn't made of
+    ///   whole cells.
+    pub fn read_png_cell_size<R>(reader: R, cell_size: IVec2, remainder: Remainder) -> Result<Self>
+    where
+        R: Read,
+    {
+        Self::decode_png(reader, |size, _| {
+            slice_cell_size(size, cell_size, remainder)
+        })
+    }
+
+    /// Loads a [SpriteSheet] from a PNG image on the disk, one line of cells at a time.
+    /// Divides the sheet according to the given number of divisions. See [SpriteSheet::read_png_div].
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::IoError] if the file could not be opened.
+    /// - Will return [Error::PngDecodingError] if the image could not be decoded.
+    /// - Will return [Error::ZeroCellSize] if `divisions` has a 0 in it, or if there are more divisions than
+    ///   pixels.
+    /// - Will return [Error::IndivisibleSheet] if `remainder` is [Remainder::Reject] and the image isn't made of
+    ///   whole cells.
+    pub fn load_streaming_div<P>(path: P, divisions: IVec2, remainder: Remainder) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Self::load_streaming(path, |reader| {
+            Self::read_png_div(reader, divisions, remainder)
+        })
+    }
+
+    /// Loads a [SpriteSheet] from a PNG image on the disk, one line of cells at a time.
+    /// Divides the sheet according to the cell size. See [SpriteSheet::read_png_div].
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::IoError] if the file could not be opened.
+    /// - Will return [Error::PngDecodingError] if the image could not be decoded.
+    /// - Will return [Error::ZeroCellSize] if `cell_size` has a 0 in it.
+    /// - Will return [Error::IndivisibleSheet] if `remainder` is [Remainder::Reject] and the image isn't made of
+    ///   whole cells.
+    pub fn load_streaming_cell_size<P>(
+        path: P,
+        cell_size: IVec2,
+        remainder: Remainder,
+    ) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Self::load_streaming(path, |reader| {
+            Self::read_png_cell_size(reader, cell_size, remainder)
+        })
+    }
+
+    /// `read` decodes the sheet from the opened file.
+    fn load_streaming<P, F>(path: P, read: F) -> Result<Self>
+    where
+        P: AsRef<Path>,
+        F: FnOnce(BufReader<File>) -> Result<Self>,
+    {
+        let mut sheet = read(BufReader::new(File::open(path.as_ref())?))?;
+        sheet.set_source_path(path.as_ref());
+        Ok(sheet)
+    }
+
+    /// Decodes a [SpriteSheet] from a PNG image read from `reader`, one line of cells at a time, using the
+    /// [SheetMetadata] embedded in it by [SpriteSheet::write_png] to divide it. See [SpriteSheet::read_png_div].
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::PngDecodingError] if the image could not be decoded.
+    /// - Will return [Error::MissingMetadata] if the image has no embedded [SheetMetadata].
+    /// - Will return [Error::InvalidMetadata] if the embedded [SheetMetadata] could not be parsed, or doesn't
+    ///   match the size of the image.
+    pub fn read_png_with_metadata<R>(reader: R) -> Result<(Self, SheetMetadata)>
+    where
+        R: Read,
+    {
+        Self::read_png_with_metadata_from(reader, None)
+    }
+
+    /// Sprites without a [SpriteSource][crate::SpriteSource] in the metadata come from `path`, if given.
+    fn read_png_with_metadata_from<R>(
+        reader: R,
+        path: Option<&Path>,
+    ) -> Result<(Self, SheetMetadata)>
+    where
+        R: Read,
+    {
+        let mut metadata = None;
+
+        let mut sheet = Self::decode_png(reader, |size, chunk| {
+            let parsed = embedded_metadata(size, chunk)?;
+            let slicing = (parsed.size, parsed.cell_size);
+
+            metadata = Some(parsed);
+            Ok(slicing)
+        })?;
+
+        let metadata = metadata.ok_or(Error::MissingMetadata)?;
+
+        if let Some(path) = path {
+            sheet.set_source_path(path);
+        }
+
+        metadata.apply_to(&mut sheet);
+
+        Ok((sheet, metadata))
+    }
+
+    /// Loads a [SpriteSheet] from a PNG image on the disk, using the [SheetMetadata] embedded in it by
+    /// [SpriteSheet::write_png] to divide it. The metadata is read first, then the image is loaded at once like
+    /// [SpriteSheet::load_cell_size] does, so that every cell is a view over it; see
+    /// [SpriteSheet::load_streaming_with_metadata] to decode it one line of cells at a time instead.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::IoError] if the file could not be opened.
+    /// - Will return [Error::PngDecodingError] if the metadata could not be decoded.
+    /// - Will return [Error::ImageError] if the image could not be decoded.
+    /// - Will return [Error::MissingMetadata] if the image has no embedded [SheetMetadata].
+    /// - Will return [Error::InvalidMetadata] if the embedded [SheetMetadata] could not be parsed, or doesn't
+    ///   match the size of the image.
+    pub fn load_with_metadata<P>(path: P) -> Result<(Self, SheetMetadata)>
+    where
+        P: AsRef<Path>,
+    {
+        let decoder = png::Decoder::new(BufReader::new(File::open(path.as_ref())?));
+        let reader = decoder.read_info()?;
+        let (width, height) = reader.info().size();
+
+        let metadata = embedded_metadata(
+            (width as usize, height as usize),
+            metadata_chunk(reader.info()),
+        )?;
+
+        let sprite = Sprite::load(path)?;
+        let mut sheet = Self::from_image(sprite, metadata.size, metadata.cell_size);
+        metadata.apply_to(&mut sheet);
+
+        Ok((sheet, metadata))
+    }
+
+    /// Loads a [SpriteSheet] from a PNG image on the disk, one line of cells at a time, using the
+    /// [SheetMetadata] embedded in it. See [SpriteSheet::read_png_with_metadata].
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::IoError] if the file could not be opened.
+    /// - Will return [Error::PngDecodingError] if the image could not be decoded.
+    /// - Will return [Error::MissingMetadata] if the image has no embedded [SheetMetadata].
+    /// - Will return [Error::InvalidMetadata] if the embedded [SheetMetadata] could not be parsed, or doesn't
+    ///   match the size of the image.
+    pub fn load_streaming_with_metadata<P>(path: P) -> Result<(Self, SheetMetadata)>
+    where
+        P: AsRef<Path>,
+    {
+        Self::read_png_with_metadata_from(
+            BufReader::new(File::open(path.as_ref())?),
+            Some(path.as_ref()),
+        )
+    }
+
+    /// `slicing` gives the divisions and the cell size of the sheet from the size of the image and the text chunk
+    /// holding its [SheetMetadata], if any.
+    fn decode_png<R, F>(reader: R, slicing: F) -> Result<Self>
+    where
+        R: Read,
+        F: FnOnce(IVec2, Option<&png::text_metadata::ITXtChunk>) -> Result<(IVec2, IVec2)>,
+    {
+        let mut decoder = png::Decoder::new(reader);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+
+        let mut reader = decoder.read_info()?;
+        let (width, height) = reader.info().size();
+        let (color, _) = reader.output_color_type();
+        let line_size = reader.output_line_size(width);
+
+        let chunk = metadata_chunk(reader.info());
+        let (divisions, cell_size) = slicing((width as usize, height as usize), chunk)?;
+
+        // Rows of interlaced images only come in order once the whole image is decoded.
+        let frame = if reader.info().interlaced {
+            let mut frame = vec![0; reader.output_buffer_size()];
+            reader.next_frame(&mut frame)?;
+            Some(frame)
+        } else {
+            None
+        };
+
+        let mut sheet = Self::new(divisions, cell_size);
+        let mut band = RgbaImage::new(width, cell_size.1 as u32);
+        let stride = width as usize * 4;
+
+        for (y, row) in sheet.rows_mut().enumerate() {
+            for (i, line) in band.chunks_exact_mut(stride).enumerate() {
+                let raw = match &frame {
+                    Some(frame) => {
+                        let start = (y * cell_size.1 + i) * line_size;
+                        &frame[start..start + line_size]
+                    }
+                    None => reader
+                        .next_row()?
+                        .ok_or_else(|| {
+                            Error::PngDecodingError(png::DecodingError::IoError(
+                                io::ErrorKind::UnexpectedEof.into(),
+                            ))
+                        })?
+                        .data(),
+                };
+
+                expand_to_rgba(color, raw, line);
+            }
+
+            for (x, cell) in row.iter_mut().enumerate() {
+                let sprite: Sprite = image::imageops::crop_imm(
+                    &band,
+                    (x * cell_size.0) as u32,
+                    0,
+                    cell_size.0 as u32,
+                    cell_size.1 as u32,
+                )
+                .to_image()
+                .into();
+
+                let sprite = sprite.with_source(SpriteSource {
+                    path: None,
+                    cell: (x, y),
+                });
+
+                if !sprite.is_empty() {
+                    *cell = SpriteCell::Sprite(sprite);
+                }
+            }
+        }
+
+        Ok(sheet)
+    }
+}
+
+/// Finds the text chunk holding the [SheetMetadata] of a PNG image, if any.
+fn metadata_chunk<'a>(info: &'a png::Info) -> Option<&'a png::text_metadata::ITXtChunk> {
+    info.utf8_text
+        .iter()
+        .find(|chunk| chunk.keyword == METADATA_KEYWORD)
+}
+
+/// Parses the [SheetMetadata] held in `chunk`, and checks that it matches an image of `size` pixels.
+fn embedded_metadata(
+    size: IVec2,
+    chunk: Option<&png::text_metadata::ITXtChunk>,
+) -> Result<SheetMetadata> {
+    let metadata: SheetMetadata = chunk.ok_or(Error::MissingMetadata)?.get_text()?.parse()?;
+    metadata.check_image_size(size)?;
+
+    Ok(metadata)
+}
+
+/// Converts a line of 8 bit pixels of the given color type to RGBA.
+fn expand_to_rgba(color: png::ColorType, raw: &[u8], rgba: &mut [u8]) {
+    let pixels = rgba.chunks_exact_mut(4);
+
+    match color {
+        png::ColorType::Rgba => rgba.copy_from_slice(&raw[..rgba.len()]),
+        png::ColorType::Rgb => {
+            for (px, rgb) in pixels.zip(raw.chunks_exact(3)) {
+                px.copy_from_slice(&[rgb[0], rgb[1], rgb[2], u8::MAX]);
+            }
+        }
+        png::ColorType::GrayscaleAlpha => {
+            for (px, la) in pixels.zip(raw.chunks_exact(2)) {
+                px.copy_from_slice(&[la[0], la[0], la[0], la[1]]);
+            }
+        }
+        png::ColorType::Grayscale => {
+            for (px, l) in pixels.zip(raw) {
+                px.copy_from_slice(&[*l, *l, *l, u8::MAX]);
+            }
+        }
+        png::ColorType::Indexed => unreachable!("indexed images are expanded by the decoder"),
+    }
+}
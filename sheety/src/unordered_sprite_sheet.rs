@@ -1,11 +1,17 @@
 use std::vec;
 
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
 use crate::{
     error::{Error, Result},
     utils::IVec2,
-    Sprite,
+    Fit, Sprite,
 };
 
+/// How many times more [Sprite]s [UnorderedSpriteSheet::retime] can make, at most.
+pub const MAX_RETIME_FACTOR: f32 = 1024.0;
+
 /// An [UnorderedSpriteSheet] encapsulate a list of [Sprite]s. It can be optained from a [SpriteSheet][crate::SpriteSheet]
 /// by using [SpriteSheet::into_unordered][crate::SpriteSheet::into_unordered], or can be created from a [Vec] of [Sprite]s.
 #[derive(Default, Debug, Clone)]
@@ -20,16 +26,17 @@ impl UnorderedSpriteSheet {
     /// # Errors
     ///
     /// - Will return [Error::EmptyUnorderedSpriteSheet] if the supplied [Vec] is empty.
-    /// The [UnorderedSpriteSheet] needs at least one [Sprite] to determine its size.
+    ///   The [UnorderedSpriteSheet] needs at least one [Sprite] to determine its size.
     /// - Will return [Error::MismatchedSpriteSize] if the supplied [Sprite]s don't each have the same size.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
+    /// # use sheety::{Sprite, UnorderedSpriteSheet};
     /// let sprite_1 = Sprite::load("path/to/a/sprite.png").unwrap();
-    /// let sprite_2 = Sprtie::load("path/to/another/sprite.png").unwrap();
+    /// let sprite_2 = Sprite::load("path/to/another/sprite.png").unwrap();
     ///
-    /// let sprites = UnorderedSpriteSheet::new(vec![sprite_1, sprite_2]);
+    /// let sprites = UnorderedSpriteSheet::new(vec![sprite_1, sprite_2]).unwrap();
     ///
     /// assert_eq!(sprites.len(), 2);
     /// ```
@@ -53,6 +60,52 @@ impl UnorderedSpriteSheet {
         Ok(Self { sprites, size })
     }
 
+    /// Creates an [UnorderedSpriteSheet] from a [Vec] of [Sprite]s of any size, fitting each of them to `size`
+    /// according to `fit` (see [Sprite::fit]).
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::EmptyUnorderedSpriteSheet] if the supplied [Vec] is empty.
+    pub fn new_fit(sprites: Vec<Sprite>, size: IVec2, fit: Fit) -> Result<Self> {
+        if sprites.is_empty() {
+            return Err(Error::EmptyUnorderedSpriteSheet);
+        }
+
+        let mut sheet = Self { sprites, size };
+        sheet.fit(size, fit);
+        Ok(sheet)
+    }
+
+    /// Fits every [Sprite] of the [UnorderedSpriteSheet] to `size` according to `fit` (see [Sprite::fit]).
+    /// [Sprite]s that already have this size are left untouched. With the `rayon` feature, [Sprite]s are fitted
+    /// in parallel.
+    pub fn fit(&mut self, size: IVec2, fit: Fit) {
+        #[cfg(feature = "rayon")]
+        let sprites = self.sprites.par_iter_mut();
+        #[cfg(not(feature = "rayon"))]
+        let sprites = self.sprites.iter_mut();
+
+        sprites.for_each(|sprite| {
+            if sprite.size() != size {
+                *sprite = sprite.fit(size, fit);
+            }
+        });
+
+        self.size = size;
+    }
+
+    /// Gets the index of the first [Sprite] named `name`.
+    pub fn position_of(&self, name: &str) -> Option<usize> {
+        self.sprites
+            .iter()
+            .position(|sprite| sprite.name() == Some(name))
+    }
+
+    /// Returns an immutable reference to the first [Sprite] named `name`.
+    pub fn get_by_name(&self, name: &str) -> Option<&Sprite> {
+        self.position_of(name).map(|i| &self.sprites[i])
+    }
+
     /// Gets the number of [Sprite]s in the [UnorderedSpriteSheet].
     #[inline(always)]
     pub fn len(&self) -> usize {
@@ -86,6 +139,108 @@ impl UnorderedSpriteSheet {
     pub fn into_inner(self) -> Vec<Sprite> {
         self.sprites
     }
+
+    /// Turns the [Sprite]s into a ping-pong loop, by appending them again in reverse order.
+    /// If `repeat_endpoints` is `false`, the first and last [Sprite]s are not repeated, so that the
+    /// animation loops smoothly: `0, 1, 2, 3` becomes `0, 1, 2, 3, 2, 1`. Otherwise, it becomes
+    /// `0, 1, 2, 3, 3, 2, 1, 0`.
+    pub fn ping_pong(&mut self, repeat_endpoints: bool) {
+        let reversed: Vec<Sprite> = if repeat_endpoints {
+            self.sprites.iter().rev().cloned().collect()
+        } else {
+            let len = self.sprites.len();
+            self.sprites
+                .iter()
+                .rev()
+                .skip(1)
+                .take(len.saturating_sub(2))
+                .cloned()
+                .collect()
+        };
+
+        self.sprites.extend(reversed);
+    }
+
+    /// Holds each [Sprite] for the amount of frames given in `holds`: the [Sprite] at index `i` is
+    /// repeated `holds[i]` times. A hold of `0` removes the [Sprite].
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::MismatchedLength] if there isn't exactly one hold per [Sprite].
+    /// - Will return [Error::EmptyUnorderedSpriteSheet] if all holds are `0`.
+    ///
+    /// In both cases, the [UnorderedSpriteSheet] is left untouched.
+    pub fn hold(&mut self, holds: &[usize]) -> Result<()> {
+        if holds.len() != self.sprites.len() {
+            return Err(Error::MismatchedLength {
+                required: self.sprites.len(),
+                provided: holds.len(),
+            });
+        }
+
+        if holds.iter().all(|hold| *hold == 0) {
+            return Err(Error::EmptyUnorderedSpriteSheet);
+        }
+
+        let sprites = std::mem::take(&mut self.sprites);
+
+        for (sprite, hold) in std::iter::zip(sprites, holds) {
+            self.sprites.extend(std::iter::repeat_n(sprite, *hold));
+        }
+
+        Ok(())
+    }
+
+    /// Resamples an animation made for `from_fps` frames per second so that it plays at the same
+    /// speed at `to_fps` frames per second, by dropping or duplicating [Sprite]s.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::InvalidFrameRate] if either frame rate is not finite and strictly positive, or if
+    ///   `to_fps` is more than [MAX_RETIME_FACTOR] times `from_fps`.
+    pub fn retime(&mut self, from_fps: f32, to_fps: f32) -> Result<()> {
+        let origins = self.retime_origins(from_fps, to_fps)?;
+
+        self.sprites = origins
+            .into_iter()
+            .map(|i| self.sprites[i].clone())
+            .collect();
+
+        Ok(())
+    }
+
+    /// Gets the index of the [Sprite] each frame comes from once retimed by [UnorderedSpriteSheet::retime], e.g.
+    /// to move [AnimationTag][crate::AnimationTag]s along with them.
+    ///
+    /// # Errors
+    ///
+    /// - Will return [Error::InvalidFrameRate] if either frame rate is not finite and strictly positive, or if
+    ///   `to_fps` is more than [MAX_RETIME_FACTOR] times `from_fps`.
+    pub fn retime_origins(&self, from_fps: f32, to_fps: f32) -> Result<Vec<usize>> {
+        for fps in [from_fps, to_fps] {
+            if !fps.is_finite() || fps <= 0.0 {
+                return Err(Error::InvalidFrameRate(fps));
+            }
+        }
+
+        // Past this, the amount of frames could overflow, or at least not fit in memory.
+        if to_fps / from_fps > MAX_RETIME_FACTOR {
+            return Err(Error::InvalidFrameRate(to_fps));
+        }
+
+        let len = self.sprites.len();
+
+        if len == 0 {
+            return Ok(Vec::new());
+        }
+
+        let ratio = from_fps / to_fps;
+        let new_len = usize::max(1, f32::round(len as f32 / ratio) as usize);
+
+        Ok((0..new_len)
+            .map(|i| usize::min(f32::floor(i as f32 * ratio) as usize, len - 1))
+            .collect())
+    }
 }
 
 impl IntoIterator for UnorderedSpriteSheet {
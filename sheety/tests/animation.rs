@@ -0,0 +1,75 @@
+use image::{Rgba, RgbaImage};
+use sheety::{error::Error, *};
+
+fn frames(count: u8) -> UnorderedSpriteSheet {
+    UnorderedSpriteSheet::new(
+        (0..count)
+            .map(|i| Sprite::from(RgbaImage::from_pixel(4, 4, Rgba([i, 0, 0, 255]))))
+            .collect(),
+    )
+    .unwrap()
+}
+
+fn order(sheet: UnorderedSpriteSheet) -> Vec<u8> {
+    sheet
+        .into_iter()
+        .map(|sprite| sprite.into_image().get_pixel(0, 0).0[0])
+        .collect()
+}
+
+#[test]
+fn ping_pong() {
+    let mut sheet = frames(4);
+    sheet.ping_pong(false);
+    assert_eq!(order(sheet), vec![0, 1, 2, 3, 2, 1]);
+
+    let mut sheet = frames(4);
+    sheet.ping_pong(true);
+    assert_eq!(order(sheet), vec![0, 1, 2, 3, 3, 2, 1, 0]);
+
+    let mut sheet = frames(1);
+    sheet.ping_pong(false);
+    assert_eq!(order(sheet), vec![0]);
+}
+
+#[test]
+fn hold() {
+    let mut sheet = frames(3);
+    sheet.hold(&[2, 0, 3]).unwrap();
+    assert_eq!(order(sheet), vec![0, 0, 2, 2, 2]);
+
+    let mut sheet = frames(3);
+    match sheet.hold(&[1, 1]).unwrap_err() {
+        Error::MismatchedLength { required, provided } => {
+            assert_eq!((required, provided), (3, 2))
+        }
+        other => panic!("expected Error::MismatchedLength, got {:?}", other),
+    }
+
+    sheet.hold(&[0, 0, 0]).unwrap_err();
+    assert_eq!(sheet.len(), 3);
+}
+
+#[test]
+fn retime() {
+    let mut sheet = frames(12);
+    sheet.retime(12.0, 8.0).unwrap();
+    assert_eq!(order(sheet), vec![0, 1, 3, 4, 6, 7, 9, 10]);
+
+    let mut sheet = frames(3);
+    assert_eq!(
+        sheet.retime_origins(6.0, 12.0).unwrap(),
+        vec![0, 0, 1, 1, 2, 2]
+    );
+    sheet.retime(6.0, 12.0).unwrap();
+    assert_eq!(order(sheet), vec![0, 0, 1, 1, 2, 2]);
+
+    frames(3).retime(0.0, 12.0).unwrap_err();
+    frames(3).retime(f32::NAN, 12.0).unwrap_err();
+    frames(3).retime(12.0, f32::INFINITY).unwrap_err();
+    frames(3).retime(1e-30, 1e30).unwrap_err();
+
+    let mut sheet = frames(2);
+    sheet.retime(1.0, MAX_RETIME_FACTOR).unwrap();
+    assert_eq!(sheet.len(), 2048);
+}
@@ -0,0 +1,130 @@
+use image::{Rgba, RgbaImage};
+use sheety::*;
+
+const MAGENTA: Rgba<u8> = Rgba([255, 0, 255, 255]);
+const RED: Rgba<u8> = Rgba([200, 30, 30, 255]);
+
+/// A 3x1 sheet of 4x4 cells on an opaque magenta background, slightly noisy like a JPEG, with a red pixel in the
+/// middle cell.
+fn sheet() -> SpriteSheet {
+    let mut image = RgbaImage::from_fn(12, 4, |x, y| {
+        let noise = ((x * 7 + y * 3) % 4) as u8;
+        Rgba([255 - noise, 0, 255 - noise, 255])
+    });
+    image.put_pixel(5, 1, RED);
+
+    SpriteSheet::from_image_cell_size(image.into(), (4, 4))
+}
+
+fn sprites(sheet: &SpriteSheet) -> usize {
+    sheet.cells().filter(|cell| !cell.is_empty()).count()
+}
+
+#[test]
+fn opaque_sheets_have_no_empty_cells() {
+    assert_eq!(sprites(&sheet()), 3);
+}
+
+#[test]
+fn clear_background_key() {
+    let mut sheet = sheet();
+    sheet.clear_background(Background::Key {
+        colour: MAGENTA,
+        tolerance: 0,
+    });
+    assert_eq!(sprites(&sheet), 3);
+
+    let mut sheet = self::sheet();
+    sheet.clear_background(Background::Key {
+        colour: MAGENTA,
+        tolerance: 3,
+    });
+    assert_eq!(sprites(&sheet), 1);
+    assert!(!sheet.get_cell((1, 0)).unwrap().is_empty());
+
+    // Pixels are left as they are.
+    let SpriteCell::Sprite(sprite) = sheet.get_cell((1, 0)).unwrap() else {
+        unreachable!()
+    };
+    assert_eq!(sprite.view().to_image().get_pixel(0, 0)[3], 255);
+}
+
+#[test]
+fn clear_background_auto() {
+    let mut sheet = sheet();
+    sheet.clear_background(Background::Auto { tolerance: 3 });
+    assert_eq!(sprites(&sheet), 1);
+    assert_eq!(sheet.into_unordered().unwrap().len(), 1);
+}
+
+#[test]
+fn clear_background_alpha() {
+    let mut image = RgbaImage::new(8, 4);
+    image.put_pixel(1, 1, Rgba([0, 0, 0, 10]));
+    image.put_pixel(5, 1, RED);
+
+    let mut sheet = SpriteSheet::from_image_cell_size(image.into(), (4, 4));
+    assert_eq!(sprites(&sheet), 2);
+
+    sheet.clear_background(Background::default());
+    assert_eq!(sprites(&sheet), 2);
+
+    sheet.clear_background(Background::Alpha(10));
+    assert_eq!(sprites(&sheet), 1);
+}
+
+#[test]
+fn key_out() {
+    let mut sheet = sheet();
+    sheet.key_out(Background::Auto { tolerance: 3 });
+    assert_eq!(sprites(&sheet), 1);
+
+    let SpriteCell::Sprite(sprite) = sheet.get_cell((1, 0)).unwrap() else {
+        unreachable!()
+    };
+    let image = sprite.view().to_image();
+    assert_eq!(*image.get_pixel(1, 1), RED);
+    assert!(image
+        .enumerate_pixels()
+        .all(|(x, y, pixel)| (x, y) == (1, 1) || pixel[3] == 0));
+}
+
+#[test]
+fn sprite_emptiness() {
+    let mut sprite = Sprite::from(RgbaImage::from_pixel(4, 4, MAGENTA));
+    assert!(!sprite.is_empty());
+    assert!(sprite.is_empty_with(Background::Auto { tolerance: 0 }));
+    assert!(!sprite.is_empty_with(Background::Alpha(254)));
+
+    // Only the pixels of the colour key become transparent.
+    sprite.image_mut().put_pixel(2, 2, RED);
+    sprite.key_out(Background::Key {
+        colour: MAGENTA,
+        tolerance: 0,
+    });
+    let image = sprite.into_image();
+    assert_eq!(image.get_pixel(0, 0)[3], 0);
+    assert_eq!(*image.get_pixel(2, 2), RED);
+}
+
+#[test]
+fn resolve_auto() {
+    let background = Background::Auto { tolerance: 2 };
+    let near = Rgba([254, 1, 255, 255]);
+
+    assert_eq!(
+        background.resolve([RED, near, MAGENTA, MAGENTA]),
+        Background::Key {
+            colour: near,
+            tolerance: 2,
+        }
+    );
+    assert_eq!(
+        Background::Auto { tolerance: 0 }.resolve([RED, MAGENTA, RED, MAGENTA]),
+        Background::Key {
+            colour: RED,
+            tolerance: 0,
+        }
+    );
+    assert_eq!(Background::Alpha(3).resolve([RED; 4]), Background::Alpha(3));
+}
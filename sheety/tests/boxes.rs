@@ -0,0 +1,142 @@
+use image::{Rgba, RgbaImage};
+use sheety::*;
+
+const HURTBOX: Rgba<u8> = Rgba([255, 0, 0, 255]);
+const HITBOX: Rgba<u8> = Rgba([0, 255, 0, 255]);
+
+fn hurtbox(position: (usize, usize), size: (usize, usize)) -> CollisionBox {
+    CollisionBox {
+        kind: BoxKind::Hurtbox,
+        position,
+        size,
+    }
+}
+
+fn boxes(sheet: &SpriteSheet, coords: (usize, usize)) -> Vec<CollisionBox> {
+    match sheet.get_cell(coords).unwrap() {
+        SpriteCell::Sprite(sprite) => sprite.boxes().to_vec(),
+        SpriteCell::Empty => Vec::new(),
+    }
+}
+
+fn fill(image: &mut RgbaImage, position: (u32, u32), size: (u32, u32), colour: Rgba<u8>) {
+    for y in position.1..position.1 + size.1 {
+        for x in position.0..position.0 + size.0 {
+            image.put_pixel(x, y, colour);
+        }
+    }
+}
+
+#[test]
+fn boxes_from_markers() {
+    let mut sheet = SpriteSheet::from_image_div(
+        RgbaImage::from_pixel(16, 8, Rgba([0, 0, 0, 255])).into(),
+        (2, 1),
+    );
+
+    let mut markers = RgbaImage::new(16, 8);
+    fill(&mut markers, (1, 1), (3, 5), HURTBOX);
+    fill(&mut markers, (5, 2), (2, 2), HITBOX);
+    fill(&mut markers, (5, 6), (1, 1), HURTBOX);
+    // An L shape makes a single box.
+    fill(&mut markers, (9, 0), (1, 4), HURTBOX);
+    fill(&mut markers, (9, 3), (4, 1), HURTBOX);
+    let markers = SpriteSheet::from_image_div(markers.into(), (2, 1));
+
+    sheet
+        .set_boxes_from_markers(&markers, HURTBOX, HITBOX)
+        .unwrap();
+
+    assert_eq!(
+        boxes(&sheet, (0, 0)),
+        vec![
+            hurtbox((1, 1), (3, 5)),
+            hurtbox((5, 6), (1, 1)),
+            CollisionBox {
+                kind: BoxKind::Hitbox,
+                position: (5, 2),
+                size: (2, 2),
+            },
+        ]
+    );
+    assert_eq!(boxes(&sheet, (1, 0)), vec![hurtbox((1, 0), (4, 4))]);
+
+    let mismatched = SpriteSheet::from_image_div(RgbaImage::new(16, 8).into(), (1, 2));
+    assert!(matches!(
+        sheet.set_boxes_from_markers(&mismatched, HURTBOX, HITBOX),
+        Err(error::Error::MismatchedSheetSize { .. })
+    ));
+}
+
+#[test]
+fn boxes_follow_flip_and_fit() {
+    let sprite = Sprite::from(RgbaImage::new(8, 4)).with_box(hurtbox((1, 1), (2, 3)));
+    assert_eq!(sprite.flip_horizontal().boxes(), &[hurtbox((5, 1), (2, 3))]);
+
+    let padded = sprite.fit((10, 6), Fit::Anchor(Anchor::BottomRight));
+    assert_eq!(padded.boxes(), &[hurtbox((3, 3), (2, 3))]);
+
+    let scaled = sprite.fit((16, 16), Fit::Scale(Anchor::Top));
+    assert_eq!(scaled.boxes(), &[hurtbox((2, 2), (4, 6))]);
+
+    // Cropping clips boxes, and drops those that end up outside of the sprite.
+    let cropped = sprite.fit((4, 4), Fit::Anchor(Anchor::Left));
+    assert_eq!(cropped.boxes(), &[hurtbox((1, 1), (2, 3))]);
+    let cropped = sprite.fit((4, 2), Fit::Anchor(Anchor::TopRight));
+    assert!(cropped.boxes().is_empty());
+    let cropped = sprite.fit((2, 2), Fit::Anchor(Anchor::TopLeft));
+    assert_eq!(cropped.boxes(), &[hurtbox((1, 1), (1, 1))]);
+}
+
+#[test]
+fn boxes_in_metadata() {
+    let sprites = vec![
+        Sprite::from(RgbaImage::from_pixel(8, 4, HITBOX)),
+        Sprite::from(RgbaImage::from_pixel(8, 4, HITBOX))
+            .with_box(hurtbox((1, 1), (2, 3)))
+            .with_box(CollisionBox {
+                kind: BoxKind::Hitbox,
+                position: (4, 0),
+                size: (4, 2),
+            }),
+    ];
+    let sheet = SpriteSheet::from_unordered(
+        UnorderedSpriteSheet::new(sprites).unwrap(),
+        Distribution::FixedColumns(2),
+    );
+
+    let metadata = sheet.metadata();
+    assert!(!metadata.info.contains_key(&0));
+    assert_eq!(metadata.info[&1].boxes.len(), 2);
+
+    let text = metadata.to_string();
+    assert!(text.contains("\nbox 1 hurtbox 1x1 2x3\nbox 1 hitbox 4x0 4x2\n"));
+    assert_eq!(text.parse::<SheetMetadata>().unwrap(), metadata);
+
+    assert_eq!(
+        sheet
+            .layout()
+            .get_cell((1, 0))
+            .unwrap()
+            .unwrap()
+            .info
+            .boxes
+            .len(),
+        2
+    );
+
+    let mut project = Vec::new();
+    sheet.write_project(&mut project, &metadata).unwrap();
+
+    let (read, _) = SpriteSheet::read_project(project.as_slice()).unwrap();
+    assert_eq!(boxes(&read, (1, 0)), boxes(&sheet, (1, 0)));
+
+    for invalid in [
+        "box 0 headbox 0x0 1x1",
+        "box 0 hitbox 0x0",
+        "box 1 hitbox 0x0 1x1",
+    ] {
+        let text = format!("sheety 1\nsize 1x1\ncell_size 1x1\nframes 0\n{invalid}\n");
+        assert!(text.parse::<SheetMetadata>().is_err(), "{invalid}");
+    }
+}
@@ -0,0 +1,31 @@
+// Each test crate only uses some of these helpers.
+#![allow(dead_code)]
+
+use image::{Rgba, RgbaImage};
+use sheety::{SpriteCell, SpriteSheet};
+
+/// A 3x2 sheet of 2x1 cells, where the first 5 cells hold frames whose red channel is their index, and the last
+/// cell is empty. Frame 2 is fully transparent, but keeps its red channel.
+pub fn sheet() -> SpriteSheet {
+    let mut sheet = SpriteSheet::new((3, 2), (2, 1));
+
+    for (i, cell) in sheet.cells_mut().take(5).enumerate() {
+        let alpha = if i == 2 { 0 } else { 255 };
+        let image = RgbaImage::from_pixel(2, 1, Rgba([i as u8, 0, 0, alpha]));
+
+        *cell = SpriteCell::Sprite(image.into());
+    }
+
+    sheet
+}
+
+/// Gets the raw pixels of each cell of `sheet`, [None] for empty cells.
+pub fn cells(sheet: &SpriteSheet) -> Vec<Option<Vec<u8>>> {
+    sheet
+        .cells()
+        .map(|cell| match cell {
+            SpriteCell::Sprite(sprite) => Some(sprite.clone().into_image().into_raw()),
+            SpriteCell::Empty => None,
+        })
+        .collect()
+}
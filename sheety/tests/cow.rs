@@ -0,0 +1,48 @@
+use image::{Rgba, RgbaImage};
+use sheety::*;
+
+const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+const BLUE: Rgba<u8> = Rgba([0, 0, 255, 255]);
+
+#[test]
+fn clones_share_pixels() {
+    let sprite = Sprite::from(RgbaImage::from_pixel(4, 4, RED));
+    let mut clone = sprite.clone();
+
+    assert!(clone.shares_pixels_with(&sprite));
+
+    clone.image_mut().put_pixel(0, 0, BLUE);
+
+    assert!(!clone.shares_pixels_with(&sprite));
+    assert_eq!(*sprite.into_image().get_pixel(0, 0), RED);
+    assert_eq!(*clone.into_image().get_pixel(0, 0), BLUE);
+}
+
+#[test]
+fn into_image_does_not_copy() {
+    let image = RgbaImage::from_pixel(4, 4, RED);
+    let pixels = image.as_raw().as_ptr();
+
+    let mut sprite = Sprite::from(image);
+    sprite.image_mut().put_pixel(0, 0, BLUE);
+
+    assert_eq!(sprite.into_image().as_raw().as_ptr(), pixels);
+}
+
+#[test]
+fn repeated_frames_share_pixels() {
+    let mut sheet = UnorderedSpriteSheet::new(vec![
+        Sprite::from(RgbaImage::from_pixel(4, 4, RED)),
+        Sprite::from(RgbaImage::from_pixel(4, 4, BLUE)),
+    ])
+    .unwrap();
+
+    sheet.hold(&[3, 1]).unwrap();
+    sheet.ping_pong(true);
+
+    let sprites = sheet.inner();
+    assert_eq!(sprites.len(), 8);
+    assert!(sprites[1].shares_pixels_with(&sprites[0]));
+    assert!(sprites[7].shares_pixels_with(&sprites[0]));
+    assert!(!sprites[3].shares_pixels_with(&sprites[0]));
+}
@@ -0,0 +1,115 @@
+use image::{Rgba, RgbaImage};
+use sheety::*;
+
+const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+
+fn sprite(colour: Rgba<u8>) -> SpriteCell {
+    SpriteCell::Sprite(RgbaImage::from_pixel(4, 4, colour).into())
+}
+
+/// A 2x2 sheet of 4x4 red cells, with the last cell empty.
+fn old() -> SpriteSheet {
+    let mut sheet = SpriteSheet::new((2, 2), (4, 4));
+
+    for coords in [(0, 0), (1, 0), (0, 1)] {
+        sheet.set_cell(coords, sprite(RED)).unwrap();
+    }
+
+    sheet
+}
+
+#[test]
+fn diff_cells() {
+    assert!(old().diff(&old()).unwrap().is_empty());
+
+    let mut new = old();
+    new.set_cell((1, 0), SpriteCell::Empty).unwrap();
+    new.set_cell((1, 1), sprite(RED)).unwrap();
+
+    let mut changed = RgbaImage::from_pixel(4, 4, RED);
+    changed.put_pixel(1, 2, Rgba([0, 0, 0, 255]));
+    changed.put_pixel(2, 3, Rgba([0, 0, 0, 0]));
+    let changed = Sprite::from(changed).with_name("idle");
+    new.set_cell((0, 1), SpriteCell::Sprite(changed)).unwrap();
+
+    let diff = old().diff(&new).unwrap();
+    assert!(!diff.is_empty());
+    assert_eq!(
+        diff.cells,
+        vec![
+            CellDiff {
+                coords: (1, 0),
+                name: None,
+                change: CellChange::Removed,
+            },
+            CellDiff {
+                coords: (0, 1),
+                name: Some("idle".to_owned()),
+                change: CellChange::Changed {
+                    pixels: 2,
+                    position: (1, 2),
+                    size: (2, 2),
+                },
+            },
+            CellDiff {
+                coords: (1, 1),
+                name: None,
+                change: CellChange::Added,
+            },
+        ]
+    );
+    assert_eq!(
+        diff.to_string(),
+        "cell 1x0: removed\ncell 0x1 (idle): 2 pixels changed in 2x2 at 1x2\ncell 1x1: added\n"
+    );
+}
+
+#[test]
+fn diff_sizes() {
+    // Fully transparent pixels are the same whatever their colour.
+    let mut transparent = old();
+    transparent
+        .set_cell((1, 1), sprite(Rgba([0, 255, 0, 0])))
+        .unwrap();
+    let mut other = old();
+    other.set_cell((1, 1), sprite(Rgba([0, 0, 0, 0]))).unwrap();
+    assert!(transparent.diff(&other).unwrap().is_empty());
+
+    let mut bigger = SpriteSheet::new((3, 1), (4, 4));
+    bigger.set_cell((0, 0), sprite(RED)).unwrap();
+    bigger.set_cell((2, 0), sprite(RED)).unwrap();
+
+    let diff = old().diff(&bigger).unwrap();
+    assert!(diff
+        .to_string()
+        .starts_with("size changed from 2x2 to 3x1 cells\n"));
+    assert_eq!(
+        diff.cells
+            .iter()
+            .map(|cell| cell.coords)
+            .collect::<Vec<_>>(),
+        vec![(1, 0), (2, 0), (0, 1)]
+    );
+
+    assert!(old().diff(&SpriteSheet::new((2, 2), (4, 2))).is_err());
+}
+
+#[test]
+fn diff_image() {
+    let mut new = old();
+    new.set_cell((1, 0), sprite(Rgba([0, 0, 255, 255])))
+        .unwrap();
+
+    let colour = Rgba([0, 255, 0, 255]);
+    let image = old().diff_image(&new, colour).unwrap();
+    assert_eq!(image.dimensions(), (8, 8));
+    assert_eq!(*image.get_pixel(0, 0), Rgba([255, 0, 0, 63]));
+    assert_eq!(*image.get_pixel(5, 1), colour);
+    assert_eq!(*image.get_pixel(5, 5), Rgba([0, 0, 0, 0]));
+
+    let image = old()
+        .diff_image(&SpriteSheet::new((1, 1), (4, 4)), colour)
+        .unwrap();
+    assert_eq!(image.dimensions(), (8, 8));
+    assert_eq!(*image.get_pixel(1, 5), colour);
+}
@@ -0,0 +1,83 @@
+use image::{Rgba, RgbaImage};
+use sheety::{error::Error, *};
+
+/// A 2x1 sprite whose left pixel is `color` and right pixel is transparent.
+fn facing(color: u8) -> Sprite {
+    let mut image = RgbaImage::new(2, 1);
+    image.put_pixel(0, 0, Rgba([color, 0, 0, 255]));
+    Sprite::from(image)
+}
+
+fn sheet() -> DirectionalSheet {
+    let mut sheet = SpriteSheet::new((2, 3), (2, 1));
+
+    for (y, color) in [10, 20, 30].into_iter().enumerate() {
+        for x in 0..2 {
+            sheet
+                .set_cell((x, y), SpriteCell::Sprite(facing(color + x as u8)))
+                .unwrap();
+        }
+    }
+
+    DirectionalSheet::new(
+        sheet,
+        vec![Direction::East, Direction::NorthEast, Direction::South],
+    )
+    .unwrap()
+}
+
+fn pixels(cell: &SpriteCell) -> (u8, u8) {
+    let image = cell.clone().sprite().unwrap().into_image();
+    (image.get_pixel(0, 0).0[0], image.get_pixel(1, 0).0[0])
+}
+
+#[test]
+fn get_cell() {
+    let sheet = sheet();
+
+    assert_eq!(
+        pixels(sheet.get_cell(Direction::NorthEast, 1).unwrap()),
+        (21, 0)
+    );
+    assert!(matches!(
+        sheet.get_cell(Direction::West, 0),
+        Err(Error::MissingDirection(Direction::West))
+    ));
+    assert!(sheet.get_cell(Direction::East, 2).is_err());
+}
+
+#[test]
+fn mirror_missing() {
+    let directions = [
+        Direction::East,
+        Direction::NorthEast,
+        Direction::NorthWest,
+        Direction::West,
+        Direction::South,
+    ];
+    let mirrored = sheet().mirror_missing(&directions).unwrap();
+
+    assert_eq!(mirrored.directions(), &directions);
+    assert_eq!(
+        pixels(mirrored.get_cell(Direction::East, 0).unwrap()),
+        (10, 0)
+    );
+    assert_eq!(
+        pixels(mirrored.get_cell(Direction::West, 0).unwrap()),
+        (0, 10)
+    );
+    assert_eq!(
+        pixels(mirrored.get_cell(Direction::NorthWest, 1).unwrap()),
+        (0, 21)
+    );
+    assert!(matches!(
+        sheet().mirror_missing(&[Direction::North]),
+        Err(Error::MissingDirection(Direction::North))
+    ));
+}
+
+#[test]
+fn parse_direction() {
+    assert_eq!("nw".parse::<Direction>().unwrap(), Direction::NorthWest);
+    assert!("up".parse::<Direction>().is_err());
+}
@@ -0,0 +1,72 @@
+use image::{Rgba, RgbaImage};
+use sheety::*;
+
+const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+
+type IVec = (u32, u32);
+
+fn opaque_bounds(sprite: Sprite) -> (IVec, IVec) {
+    let image = sprite.into_image();
+    let opaque: Vec<(u32, u32)> = image
+        .enumerate_pixels()
+        .filter(|(_, _, px)| px.0[3] != 0)
+        .map(|(x, y, _)| (x, y))
+        .collect();
+
+    (
+        (
+            opaque.iter().map(|p| p.0).min().unwrap(),
+            opaque.iter().map(|p| p.1).min().unwrap(),
+        ),
+        (
+            opaque.iter().map(|p| p.0).max().unwrap(),
+            opaque.iter().map(|p| p.1).max().unwrap(),
+        ),
+    )
+}
+
+#[test]
+fn pad() {
+    let sprite = Sprite::from(RgbaImage::from_pixel(2, 2, RED));
+
+    let fitted = sprite.fit((6, 4), Fit::Anchor(Anchor::Bottom));
+    assert_eq!(fitted.size(), (6, 4));
+    assert_eq!(opaque_bounds(fitted), ((2, 2), (3, 3)));
+
+    let fitted = sprite.fit((6, 4), Fit::Anchor(Anchor::TopLeft));
+    assert_eq!(opaque_bounds(fitted), ((0, 0), (1, 1)));
+}
+
+#[test]
+fn crop() {
+    let mut image = RgbaImage::new(4, 4);
+    image.put_pixel(3, 3, RED);
+
+    let fitted = Sprite::from(image).fit((2, 2), Fit::Anchor(Anchor::BottomRight));
+    assert_eq!(opaque_bounds(fitted), ((1, 1), (1, 1)));
+}
+
+#[test]
+fn scale() {
+    let sprite = Sprite::from(RgbaImage::from_pixel(2, 1, RED));
+
+    let fitted = sprite.fit((4, 4), Fit::Scale(Anchor::Center));
+    assert_eq!(fitted.size(), (4, 4));
+    assert_eq!(opaque_bounds(fitted), ((0, 1), (3, 2)));
+}
+
+#[test]
+fn new_fit() {
+    let sheet = UnorderedSpriteSheet::new_fit(
+        vec![
+            Sprite::from(RgbaImage::from_pixel(2, 2, RED)),
+            Sprite::from(RgbaImage::from_pixel(3, 5, RED)),
+        ],
+        (3, 3),
+        Fit::Anchor(Anchor::Center),
+    )
+    .unwrap();
+
+    assert_eq!(sheet.size(), (3, 3));
+    assert!(sheet.inner().iter().all(|sprite| sprite.size() == (3, 3)));
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use common::sheet;
+use sheety::*;
+
+fn index(cell: &SpriteCell) -> Option<u8> {
+    cell.clone()
+        .sprite()
+        .map(|sprite| sprite.into_image().get_pixel(0, 0).0[0])
+}
+
+#[test]
+fn cells() {
+    let sheet = sheet();
+
+    assert_eq!(sheet.cells().len(), 6);
+    assert_eq!(
+        sheet.cells().map(index).collect::<Vec<_>>(),
+        vec![Some(0), Some(1), Some(2), Some(3), Some(4), None]
+    );
+    assert_eq!(
+        sheet.cells().rev().map(index).collect::<Vec<_>>(),
+        vec![None, Some(4), Some(3), Some(2), Some(1), Some(0)]
+    );
+    assert_eq!(index(sheet.get_cell((1, 1)).unwrap()), Some(4));
+    assert!(sheet.get_cell((3, 0)).is_err());
+    assert!(sheet.get_cell((0, 2)).is_err());
+}
+
+#[test]
+fn into_iter() {
+    let cells = sheet().into_iter();
+    assert_eq!(cells.len(), 6);
+    assert_eq!(
+        cells.rev().map(|cell| index(&cell)).collect::<Vec<_>>(),
+        vec![None, Some(4), Some(3), Some(2), Some(1), Some(0)]
+    );
+}
+
+#[test]
+fn rows_and_columns() {
+    let mut sheet = sheet();
+
+    let rows: Vec<Vec<Option<u8>>> = sheet
+        .rows()
+        .map(|row| row.iter().map(index).collect())
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            vec![Some(0), Some(1), Some(2)],
+            vec![Some(3), Some(4), None]
+        ]
+    );
+
+    let columns: Vec<Vec<Option<u8>>> = sheet
+        .columns()
+        .rev()
+        .map(|column| column.map(index).collect())
+        .collect();
+    assert_eq!(
+        columns,
+        vec![
+            vec![Some(2), None],
+            vec![Some(1), Some(4)],
+            vec![Some(0), Some(3)]
+        ]
+    );
+
+    for row in sheet.rows_mut() {
+        row.swap(0, 2);
+    }
+    assert_eq!(index(sheet.get_cell((0, 1)).unwrap()), None);
+}
+
+#[test]
+fn set_cell_out_of_bounds() {
+    let mut sheet = sheet();
+    assert!(sheet.set_cell((3, 1), SpriteCell::Empty).is_err());
+    assert!(sheet.set_cell((2, 2), SpriteCell::Empty).is_err());
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use common::sheet;
+use sheety::*;
+
+#[test]
+fn layout() {
+    let layout = sheet().layout();
+
+    assert_eq!(layout.size, (3, 2));
+    assert_eq!(layout.cell_size, (2, 1));
+    assert_eq!(layout.frame_count(), 5);
+
+    let empty: Vec<bool> = layout.cells.iter().map(Option::is_none).collect();
+    assert_eq!(empty, vec![false, false, false, false, false, true]);
+
+    let frame = layout.get_cell((0, 1)).unwrap().unwrap();
+    assert_eq!(frame.index, 3);
+    assert_eq!(frame.position, (0, 1));
+    assert_eq!(frame.info, FrameInfo::default());
+
+    assert!(layout.get_cell((2, 1)).unwrap().is_none());
+    assert!(layout.get_cell((3, 0)).is_err());
+}
+
+#[test]
+fn layout_names() {
+    let names = ["idle", "walk0", "walk1", "walk2", "jump"].map(String::from);
+    let layout = sheet().layout().with_names(names.clone()).unwrap();
+
+    let named: Vec<&str> = layout
+        .frames()
+        .map(|frame| frame.info.name.as_deref().unwrap())
+        .collect();
+    assert_eq!(named, names);
+
+    assert!(sheet().layout().with_names(names[..4].to_vec()).is_err());
+}
+
+#[test]
+fn layout_missing_cells() {
+    let mut layout = sheet().layout();
+    layout.cells.truncate(2);
+
+    assert!(layout.get_cell((0, 0)).unwrap().is_some());
+    assert!(layout.get_cell((2, 1)).unwrap().is_none());
+    assert!(layout.get_cell((3, 1)).is_err());
+}
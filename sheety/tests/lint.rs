@@ -0,0 +1,154 @@
+use image::{Rgba, RgbaImage};
+use sheety::*;
+
+const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+
+/// A 8x8 cell with a red square of `size` pixels at `position`.
+fn square(position: (u32, u32), size: (u32, u32)) -> SpriteCell {
+    let mut image = RgbaImage::new(8, 8);
+
+    for y in position.1..position.1 + size.1 {
+        for x in position.0..position.0 + size.0 {
+            image.put_pixel(x, y, RED);
+        }
+    }
+
+    SpriteCell::Sprite(image.into())
+}
+
+#[test]
+fn lint_clean_sheet() {
+    let mut sheet = SpriteSheet::new((3, 1), (8, 8));
+
+    for x in 0..3 {
+        sheet.set_cell((x, 0), square((2, 2), (4, 4))).unwrap();
+    }
+
+    assert!(sheet.lint(&LintOptions::default()).is_empty());
+    assert_eq!(sheet.lint_image_size((24, 8)), None);
+}
+
+#[test]
+fn lint_touching_edges() {
+    let mut sheet = SpriteSheet::new((2, 1), (8, 8));
+    sheet.set_cell((0, 0), square((0, 2), (8, 4))).unwrap();
+    sheet.set_cell((1, 0), square((4, 4), (4, 4))).unwrap();
+
+    let warnings = sheet.lint(&LintOptions::default());
+    assert_eq!(
+        warnings,
+        vec![
+            LintWarning::TouchingEdges {
+                coords: (0, 0),
+                edges: vec![Edge::Right, Edge::Left],
+            },
+            LintWarning::TouchingEdges {
+                coords: (1, 0),
+                edges: vec![Edge::Right, Edge::Bottom],
+            },
+        ]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "cell 0x0: visible pixels touch the right, left edge(s), the sprite may be cut"
+    );
+}
+
+#[test]
+fn lint_almost_empty() {
+    let mut sheet = SpriteSheet::new((2, 1), (8, 8));
+    sheet.set_cell((0, 0), square((2, 2), (4, 4))).unwrap();
+    sheet.set_cell((1, 0), square((3, 3), (1, 2))).unwrap();
+
+    let warnings = sheet.lint(&LintOptions::default());
+    assert_eq!(
+        warnings,
+        vec![LintWarning::AlmostEmpty {
+            coords: (1, 0),
+            pixels: 2,
+        }]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "cell 1x0: only 2 visible pixel(s), the cell may be meant to be empty"
+    );
+
+    let options = LintOptions {
+        min_pixels: 2,
+        ..Default::default()
+    };
+    assert!(sheet.lint(&options).is_empty());
+}
+
+#[test]
+fn lint_threshold() {
+    let mut image = RgbaImage::new(8, 8);
+    image.put_pixel(0, 0, Rgba([0, 0, 0, 10]));
+    for y in 2..6 {
+        for x in 2..6 {
+            image.put_pixel(x, y, RED);
+        }
+    }
+
+    let mut sheet = SpriteSheet::new((1, 1), (8, 8));
+    sheet
+        .set_cell((0, 0), SpriteCell::Sprite(image.into()))
+        .unwrap();
+
+    assert_eq!(sheet.lint(&LintOptions::default()).len(), 1);
+
+    let options = LintOptions {
+        threshold: 10,
+        ..Default::default()
+    };
+    assert!(sheet.lint(&options).is_empty());
+}
+
+#[test]
+fn lint_inconsistent_bounds() {
+    let mut sheet = SpriteSheet::new((4, 1), (8, 8));
+
+    for x in 0..3 {
+        sheet.set_cell((x, 0), square((2, 1), (4, 6))).unwrap();
+    }
+    sheet.set_cell((3, 0), square((1, 3), (6, 2))).unwrap();
+
+    let warnings = sheet.lint(&LintOptions::default());
+    assert_eq!(
+        warnings,
+        vec![LintWarning::InconsistentBounds {
+            coords: (3, 0),
+            size: (6, 2),
+            median: (4, 6),
+        }]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "cell 3x0: the sprite is 6x2 pixels, unlike most sprites, which are about 4x6 pixels"
+    );
+
+    // Two frames don't tell which one is off.
+    let mut sheet = SpriteSheet::new((2, 1), (8, 8));
+    sheet.set_cell((0, 0), square((2, 1), (4, 6))).unwrap();
+    sheet.set_cell((1, 0), square((1, 3), (6, 2))).unwrap();
+    assert!(sheet.lint(&LintOptions::default()).is_empty());
+}
+
+#[test]
+fn lint_image_size() {
+    let image = RgbaImage::from_pixel(20, 16, RED);
+    let sheet = SpriteSheet::from_image_cell_size(image.into(), (8, 8));
+
+    let warning = sheet.lint_image_size((20, 16)).unwrap();
+    assert_eq!(
+        warning,
+        LintWarning::IndivisibleImage {
+            image_size: (20, 16),
+            cell_size: (8, 8),
+        }
+    );
+    assert_eq!(
+        warning.to_string(),
+        "the image is 20x16 pixels, which isn't made of whole cells of 8x8 pixels"
+    );
+}
@@ -0,0 +1,87 @@
+use image::{Rgba, RgbaImage};
+use sheety::*;
+
+/// A 10x2 sprite whose alpha is `x * 20 + y`.
+fn sprite() -> Sprite {
+    RgbaImage::from_fn(10, 2, |x, y| Rgba([0, 0, 0, (x * 20 + y) as u8])).into()
+}
+
+#[test]
+fn mask_threshold() {
+    let mask = sprite().collision_mask(0);
+    assert_eq!(mask.size(), (10, 2));
+    assert_eq!(mask.line_len(), 2);
+    assert!(!mask.get((0, 0)).unwrap());
+    assert!(mask.get((0, 1)).unwrap());
+    assert!(mask.get((9, 0)).unwrap());
+    assert!(mask.get((10, 0)).is_err());
+
+    // Bits are packed line after line, most significant bit first, with lines padded to whole bytes.
+    assert_eq!(
+        mask.as_bytes(),
+        &[0b0111_1111, 0b1100_0000, 0xff, 0b1100_0000]
+    );
+
+    let mask = sprite().collision_mask(100);
+    assert_eq!(
+        mask.as_bytes(),
+        &[0b0000_0011, 0b1100_0000, 0b0000_0111, 0b1100_0000]
+    );
+
+    assert!(sprite()
+        .collision_mask(255)
+        .as_bytes()
+        .iter()
+        .all(|b| *b == 0));
+    assert_eq!(
+        Sprite::from(RgbaImage::new(3, 3))
+            .collision_mask(0)
+            .as_bytes(),
+        &[0; 3]
+    );
+}
+
+#[test]
+fn mask_sheet_is_aligned() {
+    let mut sheet = SpriteSheet::new((3, 1), (10, 2));
+    sheet
+        .set_cell((0, 0), SpriteCell::Sprite(sprite().with_name("bullet")))
+        .unwrap();
+    sheet
+        .set_cell((2, 0), SpriteCell::Sprite(sprite().flip_horizontal()))
+        .unwrap();
+
+    let masks = sheet.mask_sheet(100);
+    assert_eq!(masks.size(), (3, 1));
+    assert!(masks.get_cell((1, 0)).unwrap().is_empty());
+    assert_eq!(masks.get_by_name("bullet").unwrap().size(), (10, 2));
+
+    let image = masks.into_image();
+    assert_eq!(image.get_pixel(5, 0), &Rgba([0, 0, 0, 0]));
+    assert_eq!(image.get_pixel(6, 0), &Rgba([255, 255, 255, 255]));
+    assert_eq!(image.get_pixel(23, 0), &Rgba([255, 255, 255, 255]));
+    assert_eq!(image.get_pixel(24, 0), &Rgba([0, 0, 0, 0]));
+}
+
+#[test]
+fn mask_blob() {
+    let mut sheet = SpriteSheet::new((3, 1), (10, 2));
+    sheet
+        .set_cell((1, 0), SpriteCell::Sprite(sprite()))
+        .unwrap();
+    sheet
+        .set_cell((2, 0), SpriteCell::Sprite(RgbaImage::new(10, 2).into()))
+        .unwrap();
+
+    let mut blob = Vec::new();
+    sheet.write_masks(&mut blob, 0).unwrap();
+
+    let header: Vec<u32> = blob[..12]
+        .chunks_exact(4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+        .collect();
+    assert_eq!(header, vec![10, 2, 2]);
+    assert_eq!(blob.len(), 12 + 2 * 4);
+    assert_eq!(&blob[12..16], sprite().collision_mask(0).as_bytes());
+    assert_eq!(&blob[16..], &[0; 4]);
+}
@@ -0,0 +1,195 @@
+mod common;
+
+use common::sheet;
+use sheety::*;
+
+fn reds(sprites: UnorderedSpriteSheet) -> Vec<u8> {
+    sprites
+        .into_iter()
+        .map(|sprite| sprite.into_image().get_pixel(0, 0)[0])
+        .collect()
+}
+
+#[test]
+fn metadata_text_round_trip() {
+    let metadata = sheet()
+        .metadata()
+        .with_tag("start", 0..2)
+        .unwrap()
+        .with_tag("end of it", 2..5)
+        .unwrap();
+
+    assert_eq!(metadata.frames, vec![0, 1, 2, 3, 4]);
+
+    let text = metadata.to_string();
+    assert_eq!(
+        text,
+        "sheety 1\nsize 3x2\ncell_size 2x1\nframes 0-5\ntag 0-2 start\ntag 2-5 end of it\n"
+    );
+    assert_eq!(text.parse::<SheetMetadata>().unwrap(), metadata);
+
+    let sparse = SheetMetadata {
+        frames: vec![5, 0, 1, 2, 4],
+        ..metadata.clone()
+    };
+    assert!(sparse.to_string().contains("\nframes 5,0-3,4\n"));
+    assert_eq!(sparse.to_string().parse::<SheetMetadata>().unwrap(), sparse);
+
+    assert_eq!(metadata.tag("end of it").unwrap().frames, 2..5);
+    assert!(metadata.tag("middle").is_none());
+}
+
+#[test]
+fn metadata_invalid() {
+    for text in [
+        "",
+        "sheety 2\nsize 1x1\ncell_size 1x1\nframes 0\n",
+        "sheety 1\ncell_size 1x1\nframes 0\n",
+        "sheety 1\nsize 1x1\ncell_size 1x1\nframes 1\n",
+        "sheety 1\nsize 1x1\ncell_size 1x1\nframes 0\ntag 0-2 walk\n",
+        "sheety 1\nsize 1by1\ncell_size 1x1\nframes 0\n",
+    ] {
+        assert!(text.parse::<SheetMetadata>().is_err(), "{text:?}");
+    }
+
+    // Huge ranges of frames, sizes and borders are rejected without being expanded or overflowing.
+    let max = usize::MAX;
+    for text in [
+        format!("sheety 1\nsize 1x1\ncell_size 1x1\nframes 0-{max}\n"),
+        format!("sheety 1\nsize 1x1\ncell_size 1x1\nframes {max}\n"),
+        format!("sheety 1\nsize {max}x2\ncell_size 1x1\nframes 0\n"),
+        format!("sheety 1\nsize 1x1\ncell_size 1x1\nframes 0\nnineslice 0 {max},0,1,0\n"),
+    ] {
+        assert!(matches!(
+            text.parse::<SheetMetadata>(),
+            Err(error::Error::InvalidMetadata(_))
+        ));
+    }
+
+    let unknown = "sheety 1\nsize 1x1\ncell_size 1x1\nframes 0\nfps 12\n";
+    assert!(unknown.parse::<SheetMetadata>().is_ok());
+
+    assert!(sheet().metadata().with_tag("walk", 3..6).is_err());
+    assert!(sheet().metadata().with_tag("a\nb", 0..1).is_err());
+}
+
+#[test]
+fn read_png_with_metadata() {
+    let sheet = sheet();
+    let metadata = sheet.metadata().with_tag("all", 0..5).unwrap();
+
+    let mut png = Vec::new();
+    sheet.write_png(&mut png, Some(&metadata)).unwrap();
+
+    let (read, read_metadata) = SpriteSheet::read_png_with_metadata(png.as_slice()).unwrap();
+    assert_eq!(read_metadata, metadata);
+    assert_eq!(read.size(), (3, 2));
+    assert_eq!(read.cell_size(), (2, 1));
+
+    // The transparent frame comes back as an empty cell, but the metadata keeps it as a blank frame.
+    assert!(read.get_cell((2, 0)).unwrap().is_empty());
+    assert_eq!(reds(read.into_unordered().unwrap()), vec![0, 1, 3, 4]);
+    assert_eq!(
+        reds(
+            read_metadata
+                .unordered(
+                    &SpriteSheet::read_png_div(png.as_slice(), (3, 2), Remainder::Allow).unwrap()
+                )
+                .unwrap()
+        ),
+        vec![0, 1, 0, 3, 4]
+    );
+}
+
+#[test]
+fn load_with_metadata_views() {
+    let sheet = sheet();
+    let metadata = sheet.metadata();
+    let path = std::env::temp_dir().join(format!("sheety-{}-metadata.png", std::process::id()));
+    sheet.save_png(&path, Some(&metadata)).unwrap();
+
+    let views = |sheet: &SpriteSheet| -> Vec<bool> {
+        sheet
+            .cells()
+            .filter_map(|cell| cell.clone().sprite())
+            .map(|sprite| sprite.is_view())
+            .collect()
+    };
+
+    // Cells are views over the loaded image, unless the image is streamed.
+    let (loaded, loaded_metadata) = SpriteSheet::load_with_metadata(&path).unwrap();
+    assert_eq!(loaded_metadata, metadata);
+    assert_eq!(views(&loaded), vec![true; 4]);
+
+    let (streamed, streamed_metadata) = SpriteSheet::load_streaming_with_metadata(&path).unwrap();
+    assert_eq!(streamed_metadata, metadata);
+    assert_eq!(views(&streamed), vec![false; 4]);
+
+    assert_eq!(loaded.into_image(), streamed.into_image());
+}
+
+#[test]
+fn read_png_without_metadata() {
+    let mut png = Vec::new();
+    sheet().write_png(&mut png, None).unwrap();
+
+    assert!(matches!(
+        SpriteSheet::read_png_with_metadata(png.as_slice()),
+        Err(error::Error::MissingMetadata)
+    ));
+}
+
+#[test]
+fn read_png_mismatched_metadata() {
+    let sheet = sheet();
+    let metadata = SheetMetadata {
+        cell_size: (1, 1),
+        ..sheet.metadata()
+    };
+
+    let mut png = Vec::new();
+    sheet.write_png(&mut png, Some(&metadata)).unwrap();
+
+    assert!(matches!(
+        SpriteSheet::read_png_with_metadata(png.as_slice()),
+        Err(error::Error::InvalidMetadata(_))
+    ));
+    assert!(SpriteSheet::read_png_div(png.as_slice(), (3, 2), Remainder::Allow).is_ok());
+
+    let metadata = SheetMetadata {
+        cell_size: (usize::MAX, 1),
+        ..sheet.metadata()
+    };
+
+    let mut png = Vec::new();
+    sheet.write_png(&mut png, Some(&metadata)).unwrap();
+
+    assert!(matches!(
+        SpriteSheet::read_png_with_metadata(png.as_slice()),
+        Err(error::Error::InvalidMetadata(_))
+    ));
+}
+
+#[test]
+fn tag_remap() {
+    let tag = AnimationTag {
+        name: "walk".to_owned(),
+        frames: 1..3,
+    };
+    let remap = |origins: &[usize]| {
+        tag.remap(origins.iter().copied().map(Some))
+            .map(|tag| tag.frames)
+    };
+
+    assert_eq!(remap(&[0, 1, 2, 3]), Some(1..3));
+    assert_eq!(remap(&[3, 2, 1, 0]), Some(1..3));
+    assert_eq!(remap(&[0, 2, 3]), Some(1..2));
+    assert_eq!(remap(&[0, 1, 1, 2, 2, 3]), Some(1..5));
+    assert_eq!(remap(&[0, 1, 2, 3, 2, 1]), Some(1..3));
+    assert_eq!(remap(&[0, 3]), None);
+
+    assert_eq!(
+        tag.remap([None, Some(2), None]).map(|tag| tag.frames),
+        Some(1..2)
+    );
+}
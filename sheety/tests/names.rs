@@ -0,0 +1,188 @@
+use std::path::PathBuf;
+
+use image::{Rgba, RgbaImage};
+use sheety::*;
+
+const IDLE: &str = "examples/assets/machete_idle.png";
+const SWING: &str = "examples/assets/machete_swing01.png";
+
+/// Names the sprites of `sprites` with a `prefix` followed by their index.
+fn named(prefix: &str, sprites: UnorderedSpriteSheet) -> UnorderedSpriteSheet {
+    let mut sprites = sprites;
+
+    for (i, sprite) in sprites.inner_mut().iter_mut().enumerate() {
+        sprite.set_name(Some(&format!("{prefix}_{i:02}")));
+    }
+
+    sprites
+}
+
+fn sprite(cell: &SpriteCell) -> &Sprite {
+    match cell {
+        SpriteCell::Sprite(sprite) => sprite,
+        SpriteCell::Empty => panic!("the cell is empty"),
+    }
+}
+
+fn names(sprites: &UnorderedSpriteSheet) -> Vec<Option<&str>> {
+    sprites.inner().iter().map(|sprite| sprite.name()).collect()
+}
+
+#[test]
+fn load_sets_sources() {
+    let sheet = SpriteSheet::load_cell_size(SWING, (256, 256)).unwrap();
+
+    let source = sprite(sheet.get_cell((2, 1)).unwrap()).source().unwrap();
+    assert_eq!(source.path, Some(PathBuf::from(SWING)));
+    assert_eq!(source.cell, (2, 1));
+
+    let sprite = Sprite::load(IDLE).unwrap();
+    assert_eq!(sprite.source().unwrap().cell, (0, 0));
+    assert!(Sprite::from(RgbaImage::new(1, 1)).source().is_none());
+}
+
+#[test]
+fn concat_keeps_names_and_sources() {
+    let idle = SpriteSheet::load_cell_size(IDLE, (256, 256))
+        .unwrap()
+        .into_unordered()
+        .unwrap();
+    let swing = SpriteSheet::load_cell_size(SWING, (256, 256))
+        .unwrap()
+        .into_unordered()
+        .unwrap();
+    let (idle_len, swing_len) = (idle.len(), swing.len());
+
+    let sheet = SpriteSheet::concat(
+        [named("idle", idle), named("swing", swing)].into_iter(),
+        Distribution::Packed(true),
+    )
+    .unwrap();
+
+    let coords = sheet.coords_of("swing_03").unwrap();
+    let named = sheet.get_by_name("swing_03").unwrap();
+    assert!(std::ptr::eq(sprite(sheet.get_cell(coords).unwrap()), named));
+
+    let source = named.source().unwrap();
+    assert_eq!(source.path, Some(PathBuf::from(SWING)));
+    assert_eq!(source.cell, (3, 0));
+
+    let sprites = sheet.into_unordered().unwrap();
+    assert_eq!(sprites.len(), idle_len + swing_len);
+    assert_eq!(sprites.position_of("idle_00"), Some(0));
+    assert_eq!(sprites.position_of("swing_00"), Some(idle_len));
+    assert!(sprites.get_by_name("jump_00").is_none());
+}
+
+#[test]
+fn sheet_operations_keep_names() {
+    let sprites: Vec<Sprite> = (0..3u8)
+        .map(|i| RgbaImage::from_pixel(2, 2, Rgba([i, 0, 0, 255])).into())
+        .collect();
+    let sprites = named("run", UnorderedSpriteSheet::new(sprites).unwrap());
+
+    let mut ping_pong = sprites.clone();
+    ping_pong.ping_pong(false);
+    assert_eq!(
+        names(&ping_pong),
+        vec![
+            Some("run_00"),
+            Some("run_01"),
+            Some("run_02"),
+            Some("run_01")
+        ]
+    );
+
+    let mut held = sprites.clone();
+    held.hold(&[2, 0, 1]).unwrap();
+    assert_eq!(
+        names(&held),
+        vec![Some("run_00"), Some("run_00"), Some("run_02")]
+    );
+
+    let mut retimed = sprites.clone();
+    retimed.retime(12.0, 6.0).unwrap();
+    assert_eq!(names(&retimed), vec![Some("run_00"), Some("run_02")]);
+
+    let sheet = SpriteSheet::from_unordered(ping_pong, Distribution::FixedColumns(2));
+    assert_eq!(sheet.coords_of("run_02"), Some((0, 1)));
+    assert_eq!(
+        sheet.into_unordered().unwrap().position_of("run_01"),
+        Some(1)
+    );
+
+    let zipped = SpriteSheet::zip_lines([sprites.clone(), held].into_iter()).unwrap();
+    assert_eq!(zipped.coords_of("run_02"), Some((2, 0)));
+    assert_eq!(zipped.get_by_name("run_01").unwrap().name(), Some("run_01"));
+
+    let sprite = sprites.get_by_name("run_01").unwrap();
+    assert_eq!(sprite.flip_horizontal().name(), Some("run_01"));
+    assert_eq!(
+        sprite.fit((4, 4), Fit::Anchor(Anchor::Center)).name(),
+        Some("run_01")
+    );
+}
+
+#[test]
+fn metadata_keeps_names_and_sources() {
+    let sheet = SpriteSheet::load_cell_size(SWING, (256, 256)).unwrap();
+    let sprites = named("swing", sheet.into_unordered().unwrap());
+    let sheet = SpriteSheet::from_unordered(sprites, Distribution::FixedColumns(5));
+
+    let metadata = sheet.metadata();
+    assert_eq!(metadata.info[&3].name.as_deref(), Some("swing_03"));
+    assert_eq!(metadata.info[&3].source.as_ref().unwrap().cell, (3, 0));
+
+    let text = metadata.to_string();
+    assert!(text.contains("\nname 3 swing_03\n"));
+    assert!(text.contains(&format!("\nsource 3 3x0 {SWING}\n")));
+    assert_eq!(text.parse::<SheetMetadata>().unwrap(), metadata);
+
+    let layout = sheet.layout();
+    let frame = layout.get_cell((3, 0)).unwrap().unwrap();
+    assert_eq!(frame.info.name.as_deref(), Some("swing_03"));
+    assert_eq!(frame.info.source.as_ref().unwrap().cell, (3, 0));
+
+    let mut png = Vec::new();
+    sheet.write_png(&mut png, Some(&metadata)).unwrap();
+
+    let (read, _) = SpriteSheet::read_png_with_metadata(png.as_slice()).unwrap();
+    let sprite = read.get_by_name("swing_03").unwrap();
+    assert_eq!(read.coords_of("swing_03"), Some((3, 0)));
+    assert_eq!(sprite.source().unwrap().path, Some(PathBuf::from(SWING)));
+}
+
+#[test]
+fn project_keeps_names() {
+    let sprites: Vec<Sprite> = (0..3u8)
+        .map(|i| RgbaImage::from_pixel(2, 2, Rgba([i, 0, 0, i * 100])).into())
+        .collect();
+    let sprites = named("blink", UnorderedSpriteSheet::new(sprites).unwrap());
+    let sheet = SpriteSheet::from_unordered(sprites, Distribution::FixedColumns(3));
+    let metadata = sheet.metadata();
+
+    // The first frame is fully transparent, but keeps its name.
+    let mut project = Vec::new();
+    sheet.write_project(&mut project, &metadata).unwrap();
+
+    let (read, read_metadata) = SpriteSheet::read_project(project.as_slice()).unwrap();
+    assert_eq!(read_metadata, metadata);
+    assert_eq!(read.coords_of("blink_00"), Some((0, 0)));
+
+    let sprites = read_metadata.unordered(&read).unwrap();
+    assert_eq!(
+        names(&sprites),
+        vec![Some("blink_00"), Some("blink_01"), Some("blink_02")]
+    );
+}
+
+#[test]
+fn metadata_names_out_of_bounds() {
+    let text = "sheety 1\nsize 1x1\ncell_size 1x1\nframes 0\nname 1 jump\n";
+    assert!(text.parse::<SheetMetadata>().is_err());
+
+    let text = "sheety 1\nsize 1x1\ncell_size 1x1\nframes 0\nsource 0 0x0\nname 0 a name\n";
+    let metadata = text.parse::<SheetMetadata>().unwrap();
+    assert_eq!(metadata.info[&0].name.as_deref(), Some("a name"));
+    assert_eq!(metadata.info[&0].source.as_ref().unwrap().path, None);
+}
@@ -0,0 +1,128 @@
+use image::{Rgba, RgbaImage};
+use sheety::*;
+
+/// A 5x4 panel with 1 pixel wide borders, where the red channel is `x` and the green channel is `y`.
+fn panel() -> Sprite {
+    let pixels = RgbaImage::from_fn(5, 4, |x, y| Rgba([x as u8, y as u8, 0, 255]));
+    Sprite::from(pixels).with_nine_slice(NineSlice::uniform(1))
+}
+
+fn column(sprite: &Sprite, y: u32) -> Vec<u8> {
+    let pixels = sprite.clone().into_image();
+    (0..pixels.width())
+        .map(|x| pixels.get_pixel(x, y)[0])
+        .collect()
+}
+
+#[test]
+fn nine_slice_stretch_and_tile() {
+    let stretched = panel().nine_slice_to((9, 6), SliceMode::Stretch).unwrap();
+    assert_eq!(stretched.size(), (9, 6));
+    assert_eq!(stretched.nine_slice(), Some(NineSlice::uniform(1)));
+    assert_eq!(column(&stretched, 0), vec![0, 1, 1, 2, 2, 2, 3, 3, 4]);
+
+    let tiled = panel().nine_slice_to((9, 6), SliceMode::Tile).unwrap();
+    assert_eq!(column(&tiled, 0), vec![0, 1, 2, 3, 1, 2, 3, 1, 4]);
+
+    // The bottom border keeps its pixels, the centre repeats its 2 lines.
+    let pixels = tiled.into_image();
+    let lines: Vec<u8> = (0..6).map(|y| pixels.get_pixel(0, y)[1]).collect();
+    assert_eq!(lines, vec![0, 1, 2, 1, 2, 3]);
+
+    let shrunk = panel().nine_slice_to((2, 2), SliceMode::Stretch).unwrap();
+    assert_eq!(column(&shrunk, 1), vec![0, 4]);
+}
+
+#[test]
+fn nine_slice_errors() {
+    let sprite = Sprite::from(RgbaImage::new(4, 4));
+    assert!(matches!(
+        sprite.nine_slice_to((8, 8), SliceMode::Stretch),
+        Err(error::Error::MissingNineSlice)
+    ));
+
+    assert!(matches!(
+        panel().nine_slice_to((1, 8), SliceMode::Stretch),
+        Err(error::Error::InvalidNineSlice { size: (1, 8), .. })
+    ));
+
+    assert!(!NineSlice::uniform(usize::MAX).fits((8, 8)));
+
+    let sprite = sprite.with_nine_slice(NineSlice::uniform(3));
+    assert!(matches!(
+        sprite.nine_slice_to((8, 8), SliceMode::Tile),
+        Err(error::Error::InvalidNineSlice { size: (4, 4), .. })
+    ));
+}
+
+#[test]
+fn nine_slice_follows_sprites() {
+    let borders = NineSlice {
+        left: 1,
+        top: 0,
+        right: 2,
+        bottom: 1,
+    };
+    let sprite = panel().with_nine_slice(borders).with_pivot((2.5, 4.0));
+
+    let flipped = sprite.flip_horizontal();
+    assert_eq!(
+        flipped.nine_slice(),
+        Some(NineSlice {
+            left: 2,
+            right: 1,
+            ..borders
+        })
+    );
+
+    let fitted = sprite.fit((10, 8), Fit::Scale(Anchor::TopLeft));
+    assert_eq!(
+        fitted.nine_slice(),
+        Some(NineSlice {
+            left: 2,
+            top: 0,
+            right: 4,
+            bottom: 2,
+        })
+    );
+
+    // The pivot is stretched with the centre, and stays at the bottom.
+    let resized = sprite.nine_slice_to((8, 10), SliceMode::Tile).unwrap();
+    assert_eq!(resized.pivot(), Some((4.75, 10.0)));
+    assert_eq!(resized.nine_slice(), Some(borders));
+}
+
+#[test]
+fn metadata_keeps_nine_slices() {
+    let sprites = UnorderedSpriteSheet::new(vec![panel(), panel().flip_horizontal()]).unwrap();
+    let mut sheet = SpriteSheet::from_unordered(sprites, Distribution::FixedColumns(2));
+
+    if let SpriteCell::Sprite(sprite) = sheet.get_cell_mut((1, 0)).unwrap() {
+        sprite.set_nine_slice(Some("1,0,2,1".parse().unwrap()));
+    }
+
+    let metadata = sheet.metadata();
+    let text = metadata.to_string();
+    assert!(text.contains("\nnineslice 0 1,1,1,1\nnineslice 1 1,0,2,1\n"));
+    assert_eq!(text.parse::<SheetMetadata>().unwrap(), metadata);
+
+    let layout = sheet.layout();
+    assert_eq!(
+        layout.get_cell((0, 0)).unwrap().unwrap().info.nine_slice,
+        Some(NineSlice::uniform(1))
+    );
+
+    let mut png = Vec::new();
+    sheet.write_png(&mut png, Some(&metadata)).unwrap();
+
+    let (read, _) = SpriteSheet::read_png_with_metadata(png.as_slice()).unwrap();
+    let SpriteCell::Sprite(sprite) = read.get_cell((1, 0)).unwrap() else {
+        panic!("the cell is empty");
+    };
+    assert_eq!(sprite.nine_slice().unwrap().right, 2);
+
+    let text = "sheety 1\nsize 1x1\ncell_size 4x4\nframes 0\nnineslice 0 2,0,3,0\n";
+    assert!(text.parse::<SheetMetadata>().is_err());
+    let text = "sheety 1\nsize 1x1\ncell_size 4x4\nframes 0\nnineslice 0 1,2\n";
+    assert!(text.parse::<SheetMetadata>().is_err());
+}
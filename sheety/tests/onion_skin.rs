@@ -0,0 +1,107 @@
+use image::{Rgba, RgbaImage};
+use sheety::*;
+
+/// 4 frames of 4x1 pixels, where frame `i` is a white opaque pixel at `x = i`.
+fn frames() -> UnorderedSpriteSheet {
+    let sprites = (0..4)
+        .map(|i| {
+            Sprite::from(RgbaImage::from_fn(4, 1, |x, _| match x == i {
+                true => Rgba([255, 255, 255, 255]),
+                false => Rgba([0, 0, 0, 0]),
+            }))
+            .with_name(&format!("walk_{i}"))
+        })
+        .collect();
+
+    UnorderedSpriteSheet::new(sprites).unwrap()
+}
+
+#[test]
+fn onion_skin_ghosts() {
+    let options = OnionSkin {
+        previous: 2,
+        previous_tint: Rgba([255, 0, 0, 255]),
+        next_tint: Rgba([0, 0, 255, 0]),
+        ..Default::default()
+    };
+
+    let sprite = frames().onion_skin(2, &options).unwrap();
+    assert_eq!(sprite.name(), Some("walk_2"));
+
+    let pixels = sprite.into_image();
+    assert_eq!(*pixels.get_pixel(0, 0), Rgba([255, 0, 0, 64]));
+    assert_eq!(*pixels.get_pixel(1, 0), Rgba([255, 0, 0, 128]));
+    assert_eq!(*pixels.get_pixel(2, 0), Rgba([255, 255, 255, 255]));
+    assert_eq!(*pixels.get_pixel(3, 0), Rgba([255, 255, 255, 128]));
+
+    // Without looping, the first frame has nothing before it.
+    let pixels = frames().onion_skin(0, &options).unwrap().into_image();
+    assert_eq!(pixels.get_pixel(3, 0)[3], 0);
+    assert_eq!(pixels.get_pixel(1, 0)[3], 128);
+
+    let options = OnionSkin {
+        looping: true,
+        ..options
+    };
+    let pixels = frames().onion_skin(0, &options).unwrap().into_image();
+    assert_eq!(pixels.get_pixel(3, 0)[3], 128);
+    assert_eq!(pixels.get_pixel(2, 0)[3], 64);
+
+    assert!(frames().onion_skin(4, &options).is_err());
+}
+
+#[test]
+fn onion_skin_sheet() {
+    let mut sprites = frames();
+    sprites.onion_skin_all(&OnionSkin::default());
+    assert_eq!(sprites.len(), 4);
+    assert_eq!(sprites.position_of("walk_3"), Some(3));
+
+    let pixels = sprites.inner()[3].clone().into_image();
+    let alphas: Vec<u8> = pixels.pixels().map(|pixel| pixel[3]).collect();
+    assert_eq!(alphas, vec![0, 0, 128, 255]);
+
+    // Overlapping ghosts are blended, the closest one on top.
+    let sprite = Sprite::from(RgbaImage::from_pixel(1, 1, Rgba([0, 0, 0, 255])));
+    let sprites = UnorderedSpriteSheet::new(vec![sprite.clone(); 3]).unwrap();
+    let options = OnionSkin {
+        previous: 1,
+        next: 1,
+        opacity: 0.5,
+        ..Default::default()
+    };
+    let pixels = sprites.onion_skin(1, &options).unwrap().into_image();
+    assert_eq!(*pixels.get_pixel(0, 0), Rgba([0, 0, 0, 255]));
+
+    let pixels =
+        UnorderedSpriteSheet::new(vec![RgbaImage::new(1, 1).into(), sprite.clone(), sprite])
+            .unwrap()
+            .onion_skin(0, &options)
+            .unwrap()
+            .into_image();
+    assert_eq!(*pixels.get_pixel(0, 0), Rgba([0, 128, 0, 128]));
+}
+
+#[test]
+fn onion_skin_short_loop() {
+    // Frames before and after the first one of a 3-frame loop overlap, but each is only drawn once.
+    let sprites = UnorderedSpriteSheet::new(frames().into_inner()[..3].to_vec()).unwrap();
+    let options = OnionSkin {
+        previous: 2,
+        next: 2,
+        previous_tint: Rgba([255, 0, 0, 255]),
+        next_tint: Rgba([0, 0, 255, 255]),
+        looping: true,
+        ..Default::default()
+    };
+
+    let pixels = sprites.onion_skin(0, &options).unwrap().into_image();
+    assert_eq!(*pixels.get_pixel(0, 0), Rgba([255, 255, 255, 255]));
+    assert_eq!(*pixels.get_pixel(1, 0), Rgba([0, 0, 255, 128]));
+    assert_eq!(*pixels.get_pixel(2, 0), Rgba([255, 0, 0, 128]));
+
+    let options = OnionSkin { next: 0, ..options };
+    let pixels = sprites.onion_skin(0, &options).unwrap().into_image();
+    assert_eq!(*pixels.get_pixel(1, 0), Rgba([255, 0, 0, 64]));
+    assert_eq!(*pixels.get_pixel(2, 0), Rgba([255, 0, 0, 128]));
+}
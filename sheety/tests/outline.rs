@@ -0,0 +1,174 @@
+use image::{Rgba, RgbaImage};
+use sheety::*;
+
+/// A sprite of `size` pixels, where the pixels for which `solid` returns `true` are opaque.
+fn shape<F>(size: (u32, u32), solid: F) -> Sprite
+where
+    F: Fn(u32, u32) -> bool,
+{
+    RgbaImage::from_fn(size.0, size.1, |x, y| {
+        Rgba([0, 0, 0, if solid(x, y) { 255 } else { 0 }])
+    })
+    .into()
+}
+
+/// Returns `true` if `point` is in the polygon of `outline`, by ray casting.
+fn contains(outline: &Outline, point: (f32, f32)) -> bool {
+    let vertices = &outline.vertices;
+    let n = vertices.len();
+
+    (0..n)
+        .filter(|i| {
+            let (a, b) = (vertices[*i], vertices[(i + 1) % n]);
+            (a.1 > point.1) != (b.1 > point.1)
+                && point.0 < a.0 + (point.1 - a.1) / (b.1 - a.1) * (b.0 - a.0)
+        })
+        .count()
+        % 2
+        == 1
+}
+
+#[test]
+fn rectangle_outline() {
+    let sprite = shape((8, 8), |x, y| (2..6).contains(&x) && (1..4).contains(&y));
+
+    let outlines = sprite.trace_outlines(0, 8);
+    assert_eq!(outlines.len(), 1);
+    assert_eq!(
+        outlines[0].vertices,
+        vec![(2.0, 1.0), (6.0, 1.0), (6.0, 4.0), (2.0, 4.0)]
+    );
+    assert_eq!(outlines[0].area(), 12.0);
+
+    assert!(shape((4, 4), |_, _| false).trace_outlines(0, 8).is_empty());
+}
+
+#[test]
+fn outlines_cover_pixels() {
+    // An L, a separate dot, and a ring whose hole is filled.
+    let solid = |x: u32, y: u32| {
+        (x < 3 && y < 10) || ((7..10).contains(&y) && x < 8) || (x == 12 && y == 1) || {
+            (10..15).contains(&x) && (10..15).contains(&y) && !(x == 12 && y == 12)
+        }
+    };
+    let sprite = shape((16, 16), solid);
+
+    let exact = sprite.trace_outlines(0, usize::MAX);
+    assert_eq!(exact.len(), 3);
+    assert_eq!(
+        exact.iter().map(Outline::area).sum::<f32>(),
+        30.0 + 15.0 + 1.0 + 25.0
+    );
+
+    let simplified = sprite.trace_outlines(0, 4);
+    assert_eq!(simplified.len(), 3);
+
+    for outline in simplified.iter() {
+        assert!(outline.vertices.len() <= 4);
+        assert!(outline.area() > 0.0);
+    }
+
+    for y in 0..16 {
+        for x in 0..16 {
+            if solid(x, y) {
+                let center = (x as f32 + 0.5, y as f32 + 0.5);
+                assert!(simplified.iter().any(|outline| contains(outline, center)));
+            }
+        }
+    }
+}
+
+#[test]
+fn convex_hull_and_triangles() {
+    let sprite = shape((8, 8), |x, y| x < 2 || y < 2);
+    let mut outline = sprite.trace_outlines(0, 8).remove(0);
+    assert_eq!(outline.vertices.len(), 6);
+    assert_eq!(outline.area(), 28.0);
+
+    let hull = outline.convex_hull();
+    assert_eq!(hull.vertices.len(), 5);
+    assert_eq!(hull.area(), 28.0 + 18.0);
+
+    outline.triangulate();
+    assert_eq!(outline.triangles.len(), outline.vertices.len() - 2);
+
+    let area: f32 = outline
+        .triangles
+        .iter()
+        .map(|triangle| Outline::new(triangle.map(|i| outline.vertices[i]).to_vec()).area())
+        .sum();
+    assert_eq!(area, outline.area());
+}
+
+#[test]
+fn outlines_follow_sprites() {
+    let mut sprite = shape((8, 4), |x, y| x < 3 && y < 2);
+    let outlines = sprite.trace_outlines(0, 8);
+    sprite.set_outlines(outlines);
+
+    let flipped = sprite.flip_horizontal();
+    let outline = &flipped.outlines()[0];
+    assert_eq!(outline.area(), 6.0);
+    assert!(outline.vertices.contains(&(5.0, 0.0)));
+    assert!(outline.vertices.contains(&(8.0, 2.0)));
+
+    let fitted = sprite.fit((16, 8), Fit::Scale(Anchor::TopLeft));
+    assert_eq!(fitted.outlines()[0].area(), 24.0);
+}
+
+#[test]
+fn metadata_keeps_outlines() {
+    let mut sprite = shape((4, 4), |x, y| x >= 1 && y >= 1);
+    let mut outlines = sprite.trace_outlines(0, 8);
+    outlines[0].triangulate();
+    sprite.set_outlines(outlines);
+
+    let sprites = UnorderedSpriteSheet::new(vec![sprite]).unwrap();
+    let sheet = SpriteSheet::from_unordered(sprites, Distribution::FixedColumns(1));
+
+    let metadata = sheet.metadata();
+    let text = metadata.to_string();
+    assert!(text.contains("\noutline 0 1,1 4,1 4,4 1,4 ; "));
+    assert_eq!(text.parse::<SheetMetadata>().unwrap(), metadata);
+
+    let mut png = Vec::new();
+    sheet.write_png(&mut png, Some(&metadata)).unwrap();
+
+    let (read, _) = SpriteSheet::read_png_with_metadata(png.as_slice()).unwrap();
+    let SpriteCell::Sprite(sprite) = read.get_cell((0, 0)).unwrap() else {
+        panic!("the cell is empty");
+    };
+    assert_eq!(sprite.outlines()[0].triangles.len(), 2);
+}
+
+#[test]
+fn metadata_invalid_outlines() {
+    let text = "sheety 1\nsize 1x1\ncell_size 4x4\nframes 0\noutline 0 0,0 4,0 4,4 ; 0,1,3\n";
+    assert!(text.parse::<SheetMetadata>().is_err());
+
+    let text = "sheety 1\nsize 1x1\ncell_size 4x4\nframes 0\noutline 1 0,0 4,0 4,4\n";
+    assert!(text.parse::<SheetMetadata>().is_err());
+
+    let text = "sheety 1\nsize 1x1\ncell_size 4x4\nframes 0\noutline 0 0,0 4,0 4,4 ; 0,1,2\n";
+    let metadata = text.parse::<SheetMetadata>().unwrap();
+    assert_eq!(metadata.info[&0].outlines[0].triangles, vec![[0, 1, 2]]);
+}
+
+#[test]
+fn large_outlines_cover_pixels() {
+    // A disc, whose traced outline is a staircase of about a thousand vertices.
+    let solid = |x: u32, y: u32| (x as f32 - 127.5).hypot(y as f32 - 127.5) < 120.0;
+    let sprite = shape((256, 256), solid);
+
+    let outlines = sprite.trace_outlines(0, 12);
+    assert_eq!(outlines.len(), 1);
+    assert!(outlines[0].vertices.len() <= 12);
+
+    for y in 0..256 {
+        for x in 0..256 {
+            if solid(x, y) {
+                assert!(contains(&outlines[0], (x as f32 + 0.5, y as f32 + 0.5)));
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! These tests compare against serial reference implementations, so that the output is the same with and without
+//! the `rayon` feature. Run them with `cargo test --features rayon` as well.
+
+use image::{imageops, Rgba, RgbaImage};
+use sheety::*;
+
+/// A 80x27 image of 16x9 cells of 5x3 pixels, where each pixel encodes its position,
+/// and every third cell is empty.
+fn image() -> RgbaImage {
+    RgbaImage::from_fn(80, 27, |x, y| {
+        if (x / 5 + y / 3 * 16) % 3 == 0 {
+            Rgba([0, 0, 0, 0])
+        } else {
+            Rgba([x as u8, y as u8, (x / 5) as u8, 255])
+        }
+    })
+}
+
+fn reference_is_empty(image: &RgbaImage) -> bool {
+    image.pixels().all(|px| px[3] == 0)
+}
+
+#[test]
+fn from_image_matches_reference() {
+    let image = image();
+    let sheet = SpriteSheet::from_image_cell_size(Sprite::from(image.clone()), (5, 3));
+
+    for (i, cell) in sheet.cells().enumerate() {
+        let (x, y) = ((i % 16) as u32 * 5, (i / 16) as u32 * 3);
+        let expected = imageops::crop_imm(&image, x, y, 5, 3).to_image();
+
+        match cell {
+            SpriteCell::Sprite(sprite) => {
+                assert!(!reference_is_empty(&expected));
+                assert_eq!(sprite.view().to_image(), expected);
+            }
+            SpriteCell::Empty => assert!(reference_is_empty(&expected)),
+        }
+    }
+}
+
+#[test]
+fn into_image_matches_reference() {
+    let image = image();
+    let sheet = SpriteSheet::from_image_cell_size(Sprite::from(image.clone()), (5, 3));
+    assert_eq!(sheet.into_image(), image);
+
+    let sprites: Vec<Sprite> = (0..50u8)
+        .map(|i| RgbaImage::from_pixel(5, 3, Rgba([i, 0, 0, 255])).into())
+        .collect();
+    let sheet = SpriteSheet::from_unordered(
+        UnorderedSpriteSheet::new(sprites).unwrap(),
+        Distribution::Packed(true),
+    );
+    let size = sheet.size();
+
+    let expected = RgbaImage::from_fn(size.0 as u32 * 5, size.1 as u32 * 3, |x, y| {
+        match (x / 5 + y / 3 * size.0 as u32) as u8 {
+            i if i < 50 => Rgba([i, 0, 0, 255]),
+            _ => Rgba([0, 0, 0, 0]),
+        }
+    });
+
+    assert_eq!(sheet.into_image(), expected);
+}
+
+#[test]
+fn is_empty_matches_reference() {
+    for (x, y) in [(0, 0), (7, 0), (0, 5), (7, 5), (3, 2)] {
+        let mut image = RgbaImage::new(8, 6);
+        assert!(Sprite::from(image.clone()).is_empty());
+
+        image.put_pixel(x, y, Rgba([0, 0, 0, 1]));
+        assert_eq!(
+            Sprite::from(image.clone()).is_empty(),
+            reference_is_empty(&image)
+        );
+    }
+}
+
+#[test]
+fn fit_matches_reference() {
+    let sprites: Vec<Sprite> = (1..20u32)
+        .map(|i| RgbaImage::from_pixel(i, 20 - i, Rgba([i as u8, 0, 0, 255])).into())
+        .collect();
+    let fit = Fit::Scale(Anchor::Bottom);
+
+    let sheet = UnorderedSpriteSheet::new_fit(sprites.clone(), (8, 8), fit).unwrap();
+
+    for (fitted, sprite) in sheet.into_iter().zip(sprites) {
+        assert_eq!(fitted.into_image(), sprite.fit((8, 8), fit).into_image());
+    }
+}
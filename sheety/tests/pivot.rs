@@ -0,0 +1,123 @@
+mod common;
+
+use common::sheet;
+use image::{Rgba, RgbaImage};
+use sheety::*;
+
+const MARKER: Rgba<u8> = Rgba([255, 0, 255, 255]);
+
+fn pivot(sheet: &SpriteSheet, coords: (usize, usize)) -> Option<(f32, f32)> {
+    match sheet.get_cell(coords).unwrap() {
+        SpriteCell::Sprite(sprite) => sprite.pivot(),
+        SpriteCell::Empty => None,
+    }
+}
+
+#[test]
+fn pivot_anchor() {
+    let mut sprite = Sprite::from(RgbaImage::new(4, 2));
+    assert_eq!(sprite.pivot(), None);
+
+    sprite.set_pivot_anchor(Anchor::Bottom);
+    assert_eq!(sprite.pivot(), Some((2.0, 2.0)));
+
+    sprite.set_pivot_anchor(Anchor::Pivot(0.25, 0.5));
+    assert_eq!(sprite.pivot(), Some((1.0, 1.0)));
+
+    sprite.set_pivot(None);
+    assert_eq!(sprite.pivot(), None);
+}
+
+#[test]
+fn pivot_follows_flip() {
+    let sprite = Sprite::from(RgbaImage::new(4, 2)).with_pivot((1.5, 2.0));
+    assert_eq!(sprite.flip_horizontal().pivot(), Some((2.5, 2.0)));
+    assert_eq!(
+        sprite.flip_horizontal().flip_horizontal().pivot(),
+        sprite.pivot()
+    );
+}
+
+#[test]
+fn pivot_follows_fit() {
+    let mut image = RgbaImage::new(4, 2);
+    image.put_pixel(1, 1, MARKER);
+    let sprite = Sprite::from(image).with_pivot((1.5, 1.5));
+
+    for (size, fit) in [
+        ((8, 4), Fit::Anchor(Anchor::Bottom)),
+        ((8, 8), Fit::Anchor(Anchor::TopRight)),
+        ((2, 2), Fit::Anchor(Anchor::Left)),
+        ((8, 4), Fit::Scale(Anchor::Center)),
+        ((12, 12), Fit::Scale(Anchor::Bottom)),
+    ] {
+        let fitted = sprite.fit(size, fit);
+        let (x, y) = fitted.pivot().unwrap();
+        let image = fitted.into_image();
+
+        // The pivot stays on the marker pixel.
+        assert_eq!(image.get_pixel(x as u32, y as u32), &MARKER, "{fit:?}");
+    }
+
+    let scaled = sprite.fit((8, 4), Fit::Scale(Anchor::Center));
+    assert_eq!(scaled.pivot(), Some((3.0, 3.0)));
+}
+
+#[test]
+fn pivots_from_markers() {
+    let mut sheet = sheet();
+
+    let mut markers = RgbaImage::new(6, 2);
+    markers.put_pixel(3, 0, MARKER);
+    markers.put_pixel(1, 0, Rgba([255, 0, 0, 255]));
+    let markers = SpriteSheet::from_image_div(markers.into(), (3, 2));
+
+    if let SpriteCell::Sprite(sprite) = sheet.get_cell_mut((0, 0)).unwrap() {
+        sprite.set_pivot(Some((1.0, 1.0)));
+    }
+
+    sheet.set_pivots_from_markers(&markers, MARKER).unwrap();
+
+    // The first cell has no marker of the right colour, and keeps its pivot.
+    assert_eq!(pivot(&sheet, (0, 0)), Some((1.0, 1.0)));
+    assert_eq!(pivot(&sheet, (1, 0)), Some((1.5, 0.5)));
+
+    let mismatched = SpriteSheet::from_image_div(RgbaImage::new(6, 2).into(), (1, 1));
+    assert!(matches!(
+        sheet.set_pivots_from_markers(&mismatched, MARKER),
+        Err(error::Error::MismatchedSheetSize { .. })
+    ));
+}
+
+#[test]
+fn pivots_in_metadata() {
+    let mut sheet = sheet();
+
+    for cell in sheet.cells_mut() {
+        if let SpriteCell::Sprite(sprite) = cell {
+            sprite.set_pivot_anchor(Anchor::Pivot(0.5, 0.75));
+        }
+    }
+
+    let metadata = sheet.metadata();
+    assert_eq!(metadata.info[&1].pivot, Some((1.0, 0.75)));
+
+    let text = metadata.to_string();
+    assert!(text.contains("\npivot 1 1,0.75\n"));
+    assert_eq!(text.parse::<SheetMetadata>().unwrap(), metadata);
+
+    let layout = sheet.layout();
+    assert_eq!(
+        layout.get_cell((1, 0)).unwrap().unwrap().info.pivot,
+        Some((1.0, 0.75))
+    );
+
+    let mut png = Vec::new();
+    sheet.write_png(&mut png, Some(&metadata)).unwrap();
+
+    let (read, _) = SpriteSheet::read_png_with_metadata(png.as_slice()).unwrap();
+    assert_eq!(pivot(&read, (1, 0)), Some((1.0, 0.75)));
+
+    let invalid = "sheety 1\nsize 1x1\ncell_size 1x1\nframes 0\npivot 0 1;2\n";
+    assert!(invalid.parse::<SheetMetadata>().is_err());
+}
@@ -0,0 +1,89 @@
+use image::{Rgba, RgbaImage};
+use sheety::*;
+
+const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+
+/// A 3x1 sheet of 16x8 cells, where the first and the last cells are red and the middle one is empty.
+fn sheet() -> SpriteSheet {
+    let mut sheet = SpriteSheet::new((3, 1), (16, 8));
+
+    for x in [0, 2] {
+        sheet
+            .set_cell(
+                (x, 0),
+                SpriteCell::Sprite(RgbaImage::from_pixel(16, 8, RED).into()),
+            )
+            .unwrap();
+    }
+
+    sheet
+}
+
+#[test]
+fn preview_grid_and_checkerboard() {
+    let options = PreviewOptions {
+        scale: 2,
+        labels: false,
+        ..Default::default()
+    };
+    let preview = sheet().preview(&options, None).unwrap();
+    let grid = options.grid.unwrap();
+    let [dark, light] = options.checkerboard.unwrap();
+
+    assert_eq!(preview.dimensions(), (3 * 33 + 1, 17 + 1));
+    assert_eq!(*preview.get_pixel(0, 5), grid);
+    assert_eq!(*preview.get_pixel(33, 5), grid);
+    assert_eq!(*preview.get_pixel(50, 17), grid);
+    assert_eq!(*preview.get_pixel(1, 1), RED);
+    assert_eq!(*preview.get_pixel(34, 1), dark);
+    assert_eq!(*preview.get_pixel(42, 1), light);
+
+    let options = PreviewOptions {
+        grid: None,
+        checkerboard: None,
+        labels: false,
+        ..Default::default()
+    };
+    let preview = sheet().preview(&options, None).unwrap();
+    assert_eq!(preview, sheet().into_image());
+}
+
+#[test]
+fn preview_labels() {
+    let options = PreviewOptions {
+        grid: None,
+        label_scale: 1,
+        ..Default::default()
+    };
+    let preview = sheet().preview(&options, None).unwrap();
+    let white = Rgba([255, 255, 255, 255]);
+    let black = Rgba([0, 0, 0, 255]);
+
+    // `0` then `1`, drawn after a margin of 1 pixel, and no label for the empty cell.
+    assert_eq!(*preview.get_pixel(0, 0), black);
+    assert_eq!(*preview.get_pixel(1, 1), white);
+    assert_eq!(*preview.get_pixel(2, 2), black);
+    assert_eq!(*preview.get_pixel(33, 1), black);
+    assert_eq!(*preview.get_pixel(34, 1), white);
+    assert_eq!(*preview.get_pixel(1, 7), RED);
+    assert_eq!(*preview.get_pixel(16, 0), options.checkerboard.unwrap()[0]);
+
+    // With metadata, fully transparent frames are numbered too, and names are written after the indices.
+    let mut metadata = sheet().metadata();
+    metadata.frames = vec![2, 1, 0];
+    metadata.info.entry(1).or_default().name = Some("b".to_owned());
+
+    let preview = sheet().preview(&options, Some(&metadata)).unwrap();
+    assert_eq!(*preview.get_pixel(3, 2), white);
+    assert_eq!(*preview.get_pixel(1, 2), black);
+    assert_eq!(*preview.get_pixel(17, 1), black);
+    assert_eq!(*preview.get_pixel(18, 1), white);
+    assert_eq!(*preview.get_pixel(25, 1), white);
+    assert_eq!(*preview.get_pixel(27, 1), black);
+
+    let metadata = SheetMetadata {
+        size: (1, 3),
+        ..metadata
+    };
+    assert!(sheet().preview(&options, Some(&metadata)).is_err());
+}
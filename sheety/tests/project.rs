@@ -0,0 +1,82 @@
+mod common;
+
+use common::{cells, sheet};
+use sheety::*;
+
+#[test]
+fn project_round_trip() {
+    let sheet = sheet();
+    let metadata = sheet.metadata().with_tag("loop", 1..4).unwrap();
+
+    let mut project = Vec::new();
+    sheet.write_project(&mut project, &metadata).unwrap();
+    assert!(project.starts_with(b"\x89SHEETY\n"));
+
+    let (read, read_metadata) = SpriteSheet::read_project(project.as_slice()).unwrap();
+    assert_eq!(read_metadata, metadata);
+    assert_eq!(read.size(), (3, 2));
+    assert_eq!(read.cell_size(), (2, 1));
+    assert_eq!(cells(&read), cells(&sheet));
+    assert!(read.get_cell((2, 1)).unwrap().is_empty());
+    assert!(read.get_cell((2, 0)).unwrap().is_sprite());
+}
+
+#[test]
+fn project_skips_unknown_chunks() {
+    let sheet = sheet();
+
+    let mut project = Vec::new();
+    sheet
+        .write_project(&mut project, &sheet.metadata())
+        .unwrap();
+
+    let mut extended = project[..8].to_vec();
+    extended.extend_from_slice(b"NEWS");
+    extended.extend_from_slice(&3u64.to_le_bytes());
+    extended.extend_from_slice(b"abc");
+    extended.extend_from_slice(&project[8..]);
+
+    let (read, _) = SpriteSheet::read_project(extended.as_slice()).unwrap();
+    assert_eq!(cells(&read), cells(&sheet));
+}
+
+#[test]
+fn project_invalid() {
+    let sheet = sheet();
+
+    let mut project = Vec::new();
+    sheet
+        .write_project(&mut project, &sheet.metadata())
+        .unwrap();
+
+    assert!(matches!(
+        SpriteSheet::read_project(&project[1..]),
+        Err(error::Error::InvalidProject(_))
+    ));
+    assert!(SpriteSheet::read_project(&project[..project.len() - 20]).is_err());
+
+    let mismatched = SheetMetadata {
+        size: (2, 3),
+        ..sheet.metadata()
+    };
+    assert!(sheet.write_project(Vec::new(), &mismatched).is_err());
+
+    // A grid too large for the pixels to be counted is rejected rather than overflowing.
+    let text = sheet.metadata().to_string();
+    let crafted = SheetMetadata {
+        size: (usize::MAX, 1),
+        ..sheet.metadata()
+    }
+    .to_string();
+
+    let mut overflowing = project[..8].to_vec();
+    overflowing.extend_from_slice(b"META");
+    overflowing.extend_from_slice(&(crafted.len() as u64).to_le_bytes());
+    overflowing.extend_from_slice(crafted.as_bytes());
+    overflowing.extend_from_slice(&project[20 + text.len()..]);
+
+    assert!(matches!(
+        SpriteSheet::read_project(overflowing.as_slice()),
+        Err(error::Error::InvalidMetadata(_))
+    ));
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "serde")]
+
+use image::{Rgba, RgbaImage};
+use sheety::*;
+
+fn layout() -> SheetLayout {
+    let mut sheet = SpriteSheet::new((2, 2), (3, 1));
+
+    for coords in [(0, 0), (1, 1)] {
+        sheet
+            .set_cell(
+                coords,
+                SpriteCell::Sprite(RgbaImage::from_pixel(3, 1, Rgba([255; 4])).into()),
+            )
+            .unwrap();
+    }
+
+    sheet
+        .layout()
+        .with_names(["first", "second"].map(String::from))
+        .unwrap()
+}
+
+#[test]
+fn layout_json() {
+    let layout = layout();
+    let json = serde_json::to_string(&layout).unwrap();
+
+    assert_eq!(serde_json::from_str::<SheetLayout>(&json).unwrap(), layout);
+}
+
+#[test]
+fn layout_ron() {
+    let layout = layout();
+    let ron = ron::to_string(&layout).unwrap();
+
+    assert_eq!(ron::from_str::<SheetLayout>(&ron).unwrap(), layout);
+}
+
+#[test]
+fn distribution_round_trip() {
+    for distribution in [
+        Distribution::FixedColumns(4),
+        Distribution::FixedLines(2),
+        Distribution::Packed(true),
+    ] {
+        let json = serde_json::to_string(&distribution).unwrap();
+        let from_json: Distribution = serde_json::from_str(&json).unwrap();
+        assert_eq!(format!("{from_json:?}"), format!("{distribution:?}"));
+
+        let ron = ron::to_string(&distribution).unwrap();
+        let from_ron: Distribution = ron::from_str(&ron).unwrap();
+        assert_eq!(format!("{from_ron:?}"), format!("{distribution:?}"));
+    }
+}
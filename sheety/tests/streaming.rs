@@ -0,0 +1,94 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::{cells, sheet};
+use image::{DynamicImage, GrayImage, ImageOutputFormat, Luma, Rgb, RgbImage};
+use sheety::*;
+
+#[test]
+fn write_png_matches_into_image() {
+    let sheet = sheet();
+    let mut png = Vec::new();
+    sheet.write_png(&mut png, None).unwrap();
+
+    let decoded = image::load_from_memory(&png).unwrap().into_rgba8();
+    assert_eq!(decoded, sheet.into_image());
+}
+
+#[test]
+fn read_png_round_trip() {
+    let sheet = sheet();
+    let mut png = Vec::new();
+    sheet.write_png(&mut png, None).unwrap();
+
+    // Without metadata, the fully transparent frame comes back as an empty cell.
+    let mut expected = cells(&sheet);
+    expected[2] = None;
+
+    let by_cell_size =
+        SpriteSheet::read_png_cell_size(png.as_slice(), (2, 1), Remainder::Allow).unwrap();
+    assert_eq!(by_cell_size.size(), (3, 2));
+    assert_eq!(by_cell_size.cell_size(), (2, 1));
+    assert_eq!(cells(&by_cell_size), expected);
+
+    let by_div = SpriteSheet::read_png_div(png.as_slice(), (3, 2), Remainder::Allow).unwrap();
+    assert_eq!(cells(&by_div), expected);
+}
+
+#[test]
+fn read_png_drops_remainder() {
+    let mut png = Vec::new();
+    sheet().write_png(&mut png, None).unwrap();
+
+    let sheet = SpriteSheet::read_png_cell_size(png.as_slice(), (4, 2), Remainder::Allow).unwrap();
+    assert_eq!(sheet.size(), (1, 1));
+    assert_eq!(
+        cells(&sheet)[0].as_ref().unwrap()[12..20],
+        [1, 0, 0, 255, 3, 0, 0, 255]
+    );
+}
+
+#[test]
+fn read_png_expands_colors() {
+    let mut rgb = Cursor::new(Vec::new());
+    DynamicImage::ImageRgb8(RgbImage::from_fn(2, 1, |x, _| Rgb([x as u8, 1, 2])))
+        .write_to(&mut rgb, ImageOutputFormat::Png)
+        .unwrap();
+
+    let sheet =
+        SpriteSheet::read_png_div(rgb.get_ref().as_slice(), (2, 1), Remainder::Allow).unwrap();
+    assert_eq!(
+        cells(&sheet),
+        vec![Some(vec![0, 1, 2, 255]), Some(vec![1, 1, 2, 255])]
+    );
+
+    let mut gray = Cursor::new(Vec::new());
+    DynamicImage::ImageLuma8(GrayImage::from_pixel(1, 1, Luma([7])))
+        .write_to(&mut gray, ImageOutputFormat::Png)
+        .unwrap();
+
+    let sheet =
+        SpriteSheet::read_png_div(gray.get_ref().as_slice(), (1, 1), Remainder::Allow).unwrap();
+    assert_eq!(cells(&sheet), vec![Some(vec![7, 7, 7, 255])]);
+}
+
+#[test]
+fn streaming_load_matches_load() {
+    let path = "examples/assets/machete_swing01.png";
+
+    let streamed =
+        SpriteSheet::load_streaming_cell_size(path, (256, 256), Remainder::Allow).unwrap();
+    let loaded = SpriteSheet::load_cell_size(path, (256, 256)).unwrap();
+
+    assert!(streamed.cells().all(|cell| match cell {
+        SpriteCell::Sprite(sprite) => !sprite.is_view(),
+        SpriteCell::Empty => true,
+    }));
+    assert_eq!(cells(&streamed), cells(&loaded));
+}
+
+#[test]
+fn read_png_invalid() {
+    assert!(SpriteSheet::read_png_div(&b"not a png"[..], (1, 1), Remainder::Allow).is_err());
+}
@@ -0,0 +1,130 @@
+use image::RgbaImage;
+use sheety::{error::Error, *};
+
+fn image(width: u32, height: u32) -> Sprite {
+    RgbaImage::new(width, height).into()
+}
+
+#[test]
+fn strict_whole_cells() {
+    let sheet =
+        SpriteSheet::try_from_image_cell_size(image(12, 6), (4, 3), Remainder::Reject).unwrap();
+    assert_eq!((sheet.size(), sheet.cell_size()), ((3, 2), (4, 3)));
+
+    let sheet = SpriteSheet::try_from_image_div(image(12, 6), (3, 2), Remainder::Reject).unwrap();
+    assert_eq!((sheet.size(), sheet.cell_size()), ((3, 2), (4, 3)));
+}
+
+#[test]
+fn strict_rejects_remainder() {
+    assert!(matches!(
+        SpriteSheet::try_from_image_cell_size(image(13, 6), (4, 3), Remainder::Reject),
+        Err(Error::IndivisibleSheet {
+            image_size: (13, 6),
+            cell_size: (4, 3),
+        })
+    ));
+    assert!(matches!(
+        SpriteSheet::try_from_image_div(image(12, 7), (3, 2), Remainder::Reject),
+        Err(Error::IndivisibleSheet {
+            image_size: (12, 7),
+            cell_size: (4, 3),
+        })
+    ));
+
+    // The lenient constructors drop the remainder.
+    let sheet =
+        SpriteSheet::try_from_image_cell_size(image(13, 6), (4, 3), Remainder::Allow).unwrap();
+    assert_eq!((sheet.size(), sheet.cell_size()), ((3, 2), (4, 3)));
+    let sheet = SpriteSheet::from_image_cell_size(image(13, 6), (4, 3));
+    assert_eq!((sheet.size(), sheet.cell_size()), ((3, 2), (4, 3)));
+}
+
+#[test]
+fn zero_cell_size() {
+    assert!(matches!(
+        SpriteSheet::try_from_image_cell_size(image(12, 6), (0, 3), Remainder::Reject),
+        Err(Error::ZeroCellSize)
+    ));
+    assert!(matches!(
+        SpriteSheet::try_from_image_div(image(12, 6), (3, 0), Remainder::Reject),
+        Err(Error::ZeroCellSize)
+    ));
+    assert!(matches!(
+        SpriteSheet::load_cell_size("examples/assets/machete_swing01.png", (0, 256)),
+        Err(Error::ZeroCellSize)
+    ));
+    // More divisions than pixels would make cells of 0 pixels.
+    assert!(matches!(
+        SpriteSheet::try_from_image_div(image(12, 6), (13, 1), Remainder::Reject),
+        Err(Error::ZeroCellSize)
+    ));
+
+    let mut png = Vec::new();
+    SpriteSheet::from_image_div(image(12, 6), (1, 1))
+        .write_png(&mut png, None)
+        .unwrap();
+
+    assert!(matches!(
+        SpriteSheet::read_png_div(png.as_slice(), (0, 1), Remainder::Allow),
+        Err(Error::ZeroCellSize)
+    ));
+    assert!(matches!(
+        SpriteSheet::read_png_cell_size(png.as_slice(), (4, 0), Remainder::Allow),
+        Err(Error::ZeroCellSize)
+    ));
+}
+
+#[test]
+fn strict_read_png() {
+    let mut png = Vec::new();
+    SpriteSheet::from_image_div(image(12, 6), (1, 1))
+        .write_png(&mut png, None)
+        .unwrap();
+
+    assert!(SpriteSheet::read_png_cell_size(png.as_slice(), (4, 3), Remainder::Reject).is_ok());
+    assert!(SpriteSheet::read_png_div(png.as_slice(), (3, 2), Remainder::Reject).is_ok());
+    assert!(matches!(
+        SpriteSheet::read_png_cell_size(png.as_slice(), (5, 3), Remainder::Reject),
+        Err(Error::IndivisibleSheet { .. })
+    ));
+    assert!(matches!(
+        SpriteSheet::read_png_div(png.as_slice(), (5, 2), Remainder::Reject),
+        Err(Error::IndivisibleSheet { .. })
+    ));
+}
+
+#[test]
+fn strict_load() {
+    let path = "examples/assets/machete_swing01.png";
+
+    assert!(SpriteSheet::try_from_image_cell_size(
+        Sprite::load(path).unwrap(),
+        (256, 256),
+        Remainder::Reject
+    )
+    .is_ok());
+    assert!(SpriteSheet::load_streaming_div(path, (4, 4), Remainder::Reject).is_ok());
+    assert!(matches!(
+        SpriteSheet::try_from_image_cell_size(
+            Sprite::load(path).unwrap(),
+            (200, 200),
+            Remainder::Reject
+        ),
+        Err(Error::IndivisibleSheet { .. })
+    ));
+    assert!(matches!(
+        SpriteSheet::load_streaming_cell_size(path, (200, 200), Remainder::Reject),
+        Err(Error::IndivisibleSheet { .. })
+    ));
+    assert!(matches!(
+        SpriteSheet::try_from_image_div(Sprite::load(path).unwrap(), (3, 3), Remainder::Reject),
+        Err(Error::IndivisibleSheet { .. })
+    ));
+    assert_eq!(
+        SpriteSheet::load_cell_size(path, (200, 200))
+            .unwrap()
+            .size(),
+        (5, 5)
+    );
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use image::{Rgba, RgbaImage};
+use sheety::*;
+
+/// A 4x2 image where each pixel's red channel is its x coordinate, and its green channel its y coordinate.
+fn gradient() -> RgbaImage {
+    RgbaImage::from_fn(4, 2, |x, y| Rgba([x as u8, y as u8, 0, 255]))
+}
+
+#[test]
+fn sheet_cells_are_views() {
+    let sheet = SpriteSheet::from_image_cell_size(Sprite::from(gradient()), (2, 1));
+
+    assert!(sheet.cells().all(|cell| match cell {
+        SpriteCell::Sprite(sprite) => sprite.is_view() && sprite.size() == (2, 1),
+        SpriteCell::Empty => false,
+    }));
+
+    let sprite = sheet.get_cell((1, 1)).unwrap().clone().sprite().unwrap();
+    assert_eq!(
+        sprite.view().to_image().as_raw(),
+        &[2, 1, 0, 255, 3, 1, 0, 255]
+    );
+    assert_eq!(sprite.into_image().as_raw(), &[2, 1, 0, 255, 3, 1, 0, 255]);
+
+    assert_eq!(sheet.into_image(), gradient());
+}
+
+#[test]
+fn view_image_mut() {
+    let source = Arc::new(gradient());
+    let mut sprite = Sprite::view_of(Arc::clone(&source), (1, 0), (2, 2)).unwrap();
+    let other = Sprite::view_of(Arc::clone(&source), (1, 0), (2, 2)).unwrap();
+
+    sprite.image_mut().put_pixel(0, 0, Rgba([0, 0, 0, 0]));
+
+    assert!(!sprite.is_view());
+    assert!(other.is_view());
+    assert_eq!(*other.into_image().get_pixel(0, 0), Rgba([1, 0, 0, 255]));
+    assert_eq!(*source.get_pixel(1, 0), Rgba([1, 0, 0, 255]));
+}
+
+#[test]
+fn view_out_of_bounds() {
+    let source = Arc::new(gradient());
+    assert!(Sprite::view_of(Arc::clone(&source), (3, 0), (2, 1)).is_err());
+    assert!(Sprite::view_of(source, (0, 0), (4, 2)).is_ok());
+}
+
+#[test]
+fn empty_views() {
+    let mut image = RgbaImage::new(4, 2);
+    image.put_pixel(3, 1, Rgba([255, 255, 255, 1]));
+
+    let sheet = SpriteSheet::from_image_div(Sprite::from(image), (2, 2));
+    let empty: Vec<bool> = sheet.cells().map(|cell| cell.is_empty()).collect();
+    assert_eq!(empty, vec![true, true, true, false]);
+}
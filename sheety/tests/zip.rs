@@ -0,0 +1,67 @@
+use image::{Rgba, RgbaImage};
+use sheety::*;
+
+fn frames(color: u8, count: u8) -> UnorderedSpriteSheet {
+    UnorderedSpriteSheet::new(
+        (0..count)
+            .map(|i| Sprite::from(RgbaImage::from_pixel(4, 4, Rgba([color, i, 0, 255]))))
+            .collect(),
+    )
+    .unwrap()
+}
+
+fn order(sheet: SpriteSheet) -> Vec<Option<(u8, u8)>> {
+    sheet
+        .into_iter()
+        .map(|cell| {
+            cell.sprite().map(|sprite| {
+                let px = *sprite.into_image().get_pixel(0, 0);
+                (px.0[0], px.0[1])
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn zip_lines() {
+    let sheet = SpriteSheet::zip_lines(vec![frames(0, 3), frames(1, 2)].into_iter()).unwrap();
+
+    assert_eq!(sheet.size(), (3, 2));
+    assert_eq!(
+        order(sheet),
+        vec![
+            Some((0, 0)),
+            Some((0, 1)),
+            Some((0, 2)),
+            Some((1, 0)),
+            Some((1, 1)),
+            None
+        ]
+    );
+}
+
+#[test]
+fn zip_columns() {
+    let sheet = SpriteSheet::zip_columns(vec![frames(0, 2), frames(1, 3)].into_iter()).unwrap();
+
+    assert_eq!(sheet.size(), (2, 3));
+    assert_eq!(
+        order(sheet),
+        vec![
+            Some((0, 0)),
+            Some((1, 0)),
+            Some((0, 1)),
+            Some((1, 1)),
+            None,
+            Some((1, 2))
+        ]
+    );
+}
+
+#[test]
+fn zip_mismatched() {
+    let small = UnorderedSpriteSheet::new(vec![Sprite::from(RgbaImage::new(2, 2))]).unwrap();
+
+    assert!(SpriteSheet::zip_lines(vec![frames(0, 2), small].into_iter()).is_err());
+    assert!(SpriteSheet::zip_columns(Vec::new().into_iter()).is_err());
+}